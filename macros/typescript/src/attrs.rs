@@ -0,0 +1,449 @@
+use darling::{FromMeta, ast::NestedMeta, util::Override};
+use proc_macro_error::abort;
+use syn::{ext::IdentExt, parse::ParseStream, punctuated::Punctuated, Attribute, Meta, Token};
+
+/// Serde allows the same information to be spread over several `#[serde(...)]`
+/// attributes, so all of them are merged into one list before parsing.
+fn collect_nested(attrs: &[Attribute], namespace: &str) -> Vec<NestedMeta> {
+    let mut items = Vec::new();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident(namespace)) {
+        if let Meta::List(list) = &attr.meta {
+            match list.parse_args_with(|input: ParseStream| Punctuated::<Meta, Token![,]>::parse_terminated_with(input, parse_meta_any)) {
+                Ok(nested) => items.extend(nested.into_iter().map(NestedMeta::Meta)),
+                Err(e) => abort!(list, "Could not parse #[{}] attribute: {}", namespace, e),
+            }
+        }
+    }
+
+    items
+}
+
+/// Like `Meta`'s own parser, but also accepts keywords such as `type` as keys
+fn parse_meta_any(input: ParseStream) -> syn::Result<Meta> {
+    let path = syn::Path::from(input.call(syn::Ident::parse_any)?);
+
+    if input.peek(Token![=]) {
+        Ok(Meta::NameValue(syn::MetaNameValue { path, eq_token: input.parse()?, value: input.parse()? }))
+    } else if input.peek(syn::token::Paren) {
+        let content;
+        let delimiter = syn::MacroDelimiter::Paren(syn::parenthesized!(content in input));
+        Ok(Meta::List(syn::MetaList { path, delimiter, tokens: content.parse()? }))
+    } else {
+        Ok(Meta::Path(path))
+    }
+}
+
+pub(crate) fn parse_serde<T: FromMeta>(attrs: &[Attribute]) -> T {
+    parse_namespace(attrs, "serde")
+}
+
+pub(crate) fn parse_ts<T: FromMeta>(attrs: &[Attribute]) -> T {
+    parse_namespace(attrs, "ts")
+}
+
+fn parse_namespace<T: FromMeta>(attrs: &[Attribute], namespace: &str) -> T {
+    let items = collect_nested(attrs, namespace);
+
+    match T::from_list(&items) {
+        Ok(parsed) => parsed,
+        Err(e) => abort!(e.span(), "{}", e),
+    }
+}
+
+/// `#[repr(u8)]` and friends, the discriminants of such enums can be cast to integers
+pub(crate) fn has_integer_repr(attrs: &[Attribute]) -> bool {
+    const INTEGERS: [&str; 12] = ["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+
+    attrs.iter()
+        .filter(|a| a.path().is_ident("repr"))
+        .filter_map(|a| a.parse_args_with(Punctuated::<syn::Ident, Token![,]>::parse_terminated).ok())
+        .any(|reprs| reprs.iter().any(|repr| INTEGERS.iter().any(|int| repr == int)))
+}
+
+/// The `///` comments on an item with the space rustdoc puts after `///` removed
+pub(crate) fn parse_docs(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs.iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(syn::MetaNameValue { value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }), .. }) => Some(s.value()),
+            _ => None,
+        })
+        .flat_map(|doc| doc.split('\n').map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end().to_owned()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let docs = lines.join("\n").trim_matches('\n').to_owned();
+
+    match docs.is_empty() {
+        true => None,
+        false => Some(docs),
+    }
+}
+
+/// `rename = "..."` or `rename(serialize = "...", deserialize = "...")`
+#[derive(Default, Debug, Clone)]
+pub(crate) struct Rename {
+    pub serialize: Option<String>,
+    pub deserialize: Option<String>,
+}
+
+#[derive(Default, FromMeta)]
+struct RenameParts {
+    serialize: Option<String>,
+    deserialize: Option<String>,
+}
+
+impl FromMeta for Rename {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Rename {
+            serialize: Some(value.to_owned()),
+            deserialize: Some(value.to_owned()),
+        })
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let parts = RenameParts::from_list(items)?;
+
+        Ok(Rename {
+            serialize: parts.serialize,
+            deserialize: parts.deserialize,
+        })
+    }
+}
+
+#[derive(Default, Debug, FromMeta)]
+#[darling(allow_unknown_fields)]
+pub(crate) struct SerdeContainer {
+    pub tag: Option<String>,
+    pub content: Option<String>,
+    pub untagged: Option<bool>,
+    pub rename: Option<Rename>,
+    pub rename_all: Option<Rename>,
+    pub rename_all_fields: Option<Rename>,
+    pub default: Option<Override<String>>,
+    pub transparent: Option<bool>,
+    pub into: Option<String>,
+    pub from: Option<String>,
+    pub try_from: Option<String>,
+}
+
+#[derive(Default, Debug, FromMeta)]
+#[darling(allow_unknown_fields)]
+pub(crate) struct SerdeVariant {
+    pub rename: Option<Rename>,
+    pub rename_all: Option<Rename>,
+    pub untagged: Option<bool>,
+    pub other: Option<bool>,
+    pub skip: Option<bool>,
+    pub skip_serializing: Option<bool>,
+    pub skip_deserializing: Option<bool>,
+}
+
+impl SerdeVariant {
+    pub fn is_skipped(&self) -> bool {
+        self.skip.unwrap_or(false) ||
+        (self.skip_serializing.unwrap_or(false) && self.skip_deserializing.unwrap_or(false))
+    }
+
+    pub fn is_skipped_in(&self, direction: Direction) -> bool {
+        self.skip.unwrap_or(false) || match direction {
+            Direction::Serialize => self.skip_serializing.unwrap_or(false),
+            Direction::Deserialize => self.skip_deserializing.unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Default, Debug, FromMeta)]
+#[darling(allow_unknown_fields)]
+pub(crate) struct SerdeField {
+    pub rename: Option<Rename>,
+    pub skip: Option<bool>,
+    pub skip_serializing: Option<bool>,
+    pub skip_deserializing: Option<bool>,
+    pub skip_serializing_if: Option<String>,
+    pub default: Option<Override<String>>,
+    pub flatten: Option<bool>,
+    pub with: Option<String>,
+}
+
+impl SerdeField {
+    /// `serde_with`'s `DisplayFromStr` writes the value as a string, usually to keep wide integers exact.
+    /// Only the plain adapter, `#[serde_as(as = "DisplayFromStr")]` expands to `As::<DisplayFromStr>`.
+    /// Wrapped ones like `Option<DisplayFromStr>` keep the type of the field.
+    pub fn is_display_from_str(&self) -> bool {
+        let Some(path) = self.with.as_ref().and_then(|with| syn::parse_str::<syn::Path>(with).ok()) else {
+            return false
+        };
+
+        match path.segments.last() {
+            Some(segment) if segment.ident == "As" => match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => matches!(
+                    &args.args[0],
+                    syn::GenericArgument::Type(syn::Type::Path(adapter)) if adapter.qself.is_none() &&
+                        adapter.path.segments.last().is_some_and(|s| s.ident == "DisplayFromStr" && s.arguments.is_none())
+                ),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The field is never part of the wire format
+    pub fn is_skipped(&self) -> bool {
+        self.skip.unwrap_or(false) ||
+        (self.skip_serializing.unwrap_or(false) && self.skip_deserializing.unwrap_or(false))
+    }
+
+    pub fn is_skipped_in(&self, direction: Direction) -> bool {
+        self.skip.unwrap_or(false) || match direction {
+            Direction::Serialize => self.skip_serializing.unwrap_or(false),
+            Direction::Deserialize => self.skip_deserializing.unwrap_or(false),
+        }
+    }
+
+    /// The field can be missing on the wire. Serde fills in missing `Option`s
+    /// with `None` when deserializing.
+    pub fn is_optional_in(&self, direction: Direction, container_default: bool, ty: &syn::Type) -> bool {
+        match direction {
+            Direction::Serialize => self.skip_serializing_if.is_some(),
+            Direction::Deserialize => container_default || self.default.is_some() || is_option(ty),
+        }
+    }
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(x) if x.qself.is_none() => x.path.segments.last()
+            .map(|s| s.ident == "Option" && matches!(s.arguments, syn::PathArguments::AngleBracketed(_)))
+            .unwrap_or(false),
+        syn::Type::Paren(x) => is_option(&x.elem),
+        syn::Type::Group(x) => is_option(&x.elem),
+        _ => false,
+    }
+}
+
+/// Serde can read and write the same type differently, the derive describes
+/// each direction separately.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Direction {
+    /// What the server sends
+    Serialize,
+    /// What the server accepts
+    Deserialize,
+}
+
+/// `#[ts(...)]` on a struct or enum
+#[derive(Default, Debug, FromMeta)]
+pub(crate) struct TsContainer {
+    /// The name of the type in TypeScript
+    pub rename: Option<String>,
+    /// Written instead of the type's own shape
+    #[darling(rename = "type")]
+    pub typ: Option<String>,
+    /// `union`, `enum` or `const`, how a fieldless enum is written
+    pub enum_style: Option<String>,
+    /// Exported by routers calling `add_exported_types`, also when no route refers to the type
+    pub export: Option<bool>,
+    /// A newtype written as `Inner & { readonly __brand: "Name" }` with a constructor
+    pub brand: Option<bool>,
+    /// A fieldless enum written as its discriminants, for enums serialized with `serde_repr`
+    pub repr: Option<bool>,
+}
+
+/// `#[ts(...)]` on an enum variant
+#[derive(Default, Debug, FromMeta)]
+pub(crate) struct TsVariant {
+    /// Shown in the UI instead of the variant name
+    pub label: Option<String>,
+}
+
+/// `#[ts(...)]` on a field, these only change the generated client
+#[derive(Default, Debug, FromMeta)]
+pub(crate) struct TsField {
+    #[darling(rename = "type")]
+    pub typ: Option<String>,
+    pub rename: Option<String>,
+    pub optional: Option<bool>,
+    pub nullable: Option<bool>,
+    pub inline: Option<bool>,
+    pub skip: Option<bool>,
+}
+
+impl TsField {
+    /// The `#[ts(...)]` of a field, `#[serde_as(as = "DisplayFromStr")]` fields default to `string`
+    pub fn parse(attrs: &[Attribute]) -> Self {
+        let mut ts: TsField = parse_ts(attrs);
+        if ts.typ.is_none() && parse_serde::<SerdeField>(attrs).is_display_from_str() {
+            ts.typ = Some(String::from("string"));
+        }
+        ts
+    }
+
+    /// The field type is not looked at, so it doesn't have to implement `TypescriptType`
+    pub fn replaces_type(&self) -> bool {
+        self.typ.is_some() || self.skip.unwrap_or(false)
+    }
+}
+
+/// The case conversions supported by serde's `rename_all`.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RenameRule {
+    #[default]
+    None,
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        let rule = match rule {
+            "lowercase" => RenameRule::LowerCase,
+            "UPPERCASE" => RenameRule::UpperCase,
+            "PascalCase" => RenameRule::PascalCase,
+            "camelCase" => RenameRule::CamelCase,
+            "snake_case" => RenameRule::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            "kebab-case" => RenameRule::KebabCase,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebabCase,
+            _ => return None,
+        };
+
+        Some(rule)
+    }
+
+    /// Variants are expected to be written in PascalCase.
+    pub fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::None | RenameRule::PascalCase => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_ascii_lowercase(),
+            RenameRule::UpperCase => variant.to_ascii_uppercase(),
+            RenameRule::CamelCase => variant[..1].to_ascii_lowercase() + &variant[1..],
+            RenameRule::SnakeCase => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnakeCase => RenameRule::SnakeCase.apply_to_variant(variant).to_ascii_uppercase(),
+            RenameRule::KebabCase => RenameRule::SnakeCase.apply_to_variant(variant).replace('_', "-"),
+            RenameRule::ScreamingKebabCase => RenameRule::ScreamingSnakeCase.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+
+    /// Fields are expected to be written in snake_case.
+    pub fn apply_to_field(self, field: &str) -> String {
+        match self {
+            RenameRule::None | RenameRule::LowerCase | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::UpperCase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for ch in field.chars() {
+                    if ch == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(ch.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(ch);
+                    }
+                }
+                pascal
+            }
+            RenameRule::CamelCase => {
+                let pascal = RenameRule::PascalCase.apply_to_field(field);
+                pascal[..1].to_ascii_lowercase() + &pascal[1..]
+            }
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+}
+
+/// A pair of rename rules, one per serialization direction.
+#[derive(Default, Debug, Clone, Copy)]
+pub(crate) struct RenameAll {
+    pub serialize: RenameRule,
+    pub deserialize: RenameRule,
+}
+
+impl RenameAll {
+    pub fn from_attr(rename: &Option<Rename>) -> Self {
+        let parse = |rule: &Option<String>| match rule {
+            Some(r) => RenameRule::from_str(r).unwrap_or_else(|| abort!(
+                proc_macro2::Span::call_site(),
+                "unknown rename rule `rename_all = {:?}`, expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\", \"SCREAMING-KEBAB-CASE\"",
+                r
+            )),
+            None => RenameRule::None,
+        };
+
+        match rename {
+            Some(r) => RenameAll {
+                serialize: parse(&r.serialize),
+                deserialize: parse(&r.deserialize),
+            },
+            None => RenameAll::default(),
+        }
+    }
+
+    /// Falls back to `other` for every direction this rule set leaves untouched.
+    pub fn or(self, other: RenameAll) -> Self {
+        RenameAll {
+            serialize: if self.serialize == RenameRule::None { other.serialize } else { self.serialize },
+            deserialize: if self.deserialize == RenameRule::None { other.deserialize } else { self.deserialize },
+        }
+    }
+}
+
+/// The name an item has on the wire. Serde can use different names per direction.
+#[derive(Debug, Clone)]
+pub(crate) struct Names {
+    pub serialize: String,
+    pub deserialize: String,
+}
+
+impl Names {
+    fn resolve(rename: &Option<Rename>, rule: RenameAll, raw: &str, apply: fn(RenameRule, &str) -> String) -> Self {
+        let explicit = rename.clone().unwrap_or_default();
+
+        Names {
+            serialize: explicit.serialize.unwrap_or_else(|| apply(rule.serialize, raw)),
+            deserialize: explicit.deserialize.unwrap_or_else(|| apply(rule.deserialize, raw)),
+        }
+    }
+
+    pub fn get(self, direction: Direction) -> String {
+        match direction {
+            Direction::Serialize => self.serialize,
+            Direction::Deserialize => self.deserialize,
+        }
+    }
+
+    pub fn container(rename: &Option<Rename>, ident: &syn::Ident) -> Self {
+        Names::resolve(rename, RenameAll::default(), &unraw(ident), |_, raw| raw.to_owned())
+    }
+
+    pub fn variant(rename: &Option<Rename>, rule: RenameAll, ident: &syn::Ident) -> Self {
+        Names::resolve(rename, rule, &unraw(ident), RenameRule::apply_to_variant)
+    }
+
+    pub fn field(rename: &Option<Rename>, rule: RenameAll, ident: &syn::Ident) -> Self {
+        Names::resolve(rename, rule, &unraw(ident), RenameRule::apply_to_field)
+    }
+}
+
+fn unraw(ident: &syn::Ident) -> String {
+    let name = ident.to_string();
+    name.strip_prefix("r#").map(str::to_owned).unwrap_or(name)
+}
@@ -0,0 +1,222 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens as _};
+use syn::{parse_quote, visit_mut::{self, VisitMut}};
+
+/// Generic types are described once with their type parameters replaced by
+/// placeholder types. The placeholders show up as the parameter names in TypeScript.
+pub(crate) struct Placeholders {
+    params: Vec<(syn::Ident, syn::Ident)>,
+    /// The trait bounds of every parameter, from the parameter list and the where clause
+    bounds: Vec<Vec<syn::TraitBound>>,
+}
+
+impl Placeholders {
+    pub fn new(generics: &syn::Generics) -> Self {
+        let params: Vec<_> = generics.type_params()
+            .map(|p| (p.ident.clone(), format_ident!("__TsParam{}", p.ident)))
+            .collect();
+
+        let bounds = generics.type_params()
+            .map(|p| {
+                let predicates = generics.where_clause.iter()
+                    .flat_map(|clause| clause.predicates.iter())
+                    .filter_map(|predicate| match predicate {
+                        syn::WherePredicate::Type(t) if is_param(&t.bounded_ty, &p.ident) => Some(&t.bounds),
+                        _ => None
+                    })
+                    .flatten();
+
+                p.bounds.iter().chain(predicates)
+                    .filter_map(|bound| match bound {
+                        syn::TypeParamBound::Trait(t) if matches!(t.modifier, syn::TraitBoundModifier::None) => Some(t.clone()),
+                        _ => None
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Placeholders { params, bounds }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// The type parameters as written on the type
+    pub fn params(&self) -> Vec<syn::Ident> {
+        self.params.iter().map(|(param, _)| param.clone()).collect()
+    }
+
+    /// The names of the parameters in the TypeScript declaration
+    pub fn ts_names(&self) -> Vec<String> {
+        self.params.iter().map(|(param, _)| param.to_string()).collect()
+    }
+
+    /// The placeholder types, they are generic names for the rename checks
+    pub fn placeholder_names(&self) -> Vec<String> {
+        self.params.iter().map(|(_, placeholder)| placeholder.to_string()).collect()
+    }
+
+    /// The local placeholder types and their `TypescriptType` implementations. The placeholders
+    /// implement the traits their parameter is bounded by, other generic types with the same
+    /// bounds can be described with them.
+    pub fn definitions(&self) -> TokenStream {
+        let definitions = self.params.iter().zip(self.bounds.iter()).map(|((param, placeholder), bounds)| {
+            let name = param.to_string();
+            let bound_impls = bounds.iter().map(|bound| bound_impl(placeholder, bound));
+
+            quote! {
+                #[allow(dead_code)]
+                #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+                struct #placeholder;
+
+                impl ::std::fmt::Display for #placeholder {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        f.write_str(#name)
+                    }
+                }
+
+                #(#bound_impls)*
+
+                impl ::tsclient::types::TypescriptType for #placeholder {
+                    fn get_definition(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::tsclient::types::builder::HasIndexed {
+                        ::tsclient::types::impls::boilerplate_simple_definition::<Self>(::tsclient::types::model::Type::Param(String::from(#name)), registry)
+                    }
+                    fn name() -> ::std::string::String {
+                        String::from(#name)
+                    }
+                    fn ts_name(_: ::tsclient::types::model::WideIntegers) -> ::std::string::String {
+                        String::from(#name)
+                    }
+                    fn hash(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::std::primitive::u64 {
+                        ::tsclient::types::impls::boilerplate_simple_hash::<Self>(registry)
+                    }
+                }
+            }
+        });
+
+        quote!(#(#definitions)*)
+    }
+
+    pub fn substitute_data(&self, data: &syn::Data) -> syn::Data {
+        let mut data = data.clone();
+        Substitute(self).visit_data_mut(&mut data);
+        data
+    }
+
+    pub fn substitute(&self, ty: &syn::Type) -> syn::Type {
+        let mut ty = ty.clone();
+        Substitute(self).visit_type_mut(&mut ty);
+        ty
+    }
+}
+
+fn is_param(ty: &syn::Type, param: &syn::Ident) -> bool {
+    matches!(ty, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident(param))
+}
+
+/// Implements a bound of the parameter for its placeholder. Derivable std traits, auto traits and
+/// `TypescriptType` are implemented already, serde's traits are written out and any other trait
+/// is implemented with its default items.
+fn bound_impl(placeholder: &syn::Ident, bound: &syn::TraitBound) -> TokenStream {
+    let trait_name = bound.path.segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+
+    match trait_name.as_str() {
+        "Clone" | "Copy" | "Debug" | "Default" | "PartialEq" | "Eq" | "PartialOrd" | "Ord" | "Hash" | "Display" |
+        "Send" | "Sync" | "Sized" | "Unpin" | "TypescriptType" => quote!(),
+        "Serialize" => quote! {
+            impl ::serde::Serialize for #placeholder {
+                fn serialize<__S: ::serde::Serializer>(&self, serializer: __S) -> ::std::result::Result<__S::Ok, __S::Error> {
+                    serializer.serialize_unit()
+                }
+            }
+        },
+        "Deserialize" | "DeserializeOwned" => quote! {
+            impl<'de> ::serde::Deserialize<'de> for #placeholder {
+                fn deserialize<__D: ::serde::Deserializer<'de>>(deserializer: __D) -> ::std::result::Result<Self, __D::Error> {
+                    <() as ::serde::Deserialize>::deserialize(deserializer).map(|_| #placeholder)
+                }
+            }
+        },
+        _ => {
+            let path = &bound.path;
+            quote!(impl #path for #placeholder {})
+        }
+    }
+}
+
+/// The generics of the impl, every type parameter has to be describable itself.
+/// Lifetimes, const parameters and the where clause are kept as written.
+pub(crate) fn bounded(generics: &syn::Generics) -> syn::Generics {
+    let mut generics = generics.clone();
+
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::tsclient::types::TypescriptType));
+        param.bounds.push(parse_quote!('static));
+    }
+
+    generics
+}
+
+/// `Self` with every lifetime set to `'static`, `TypeId` only works for `'static` types
+pub(crate) fn static_self(ident: &syn::Ident, generics: &syn::Generics) -> syn::Type {
+    if generics.params.is_empty() {
+        return parse_quote!(#ident)
+    }
+
+    let args = generics.params.iter().map(|param| match param {
+        syn::GenericParam::Lifetime(_) => quote!('static),
+        syn::GenericParam::Type(t) => t.ident.to_token_stream(),
+        syn::GenericParam::Const(c) => c.ident.to_token_stream(),
+    });
+
+    parse_quote!(#ident<#(#args),*>)
+}
+
+/// Types are described without a value, so borrowed fields can be described as `'static`
+pub(crate) fn erase_lifetimes_data(data: &syn::Data) -> syn::Data {
+    let mut data = data.clone();
+    EraseLifetimes.visit_data_mut(&mut data);
+    data
+}
+
+pub(crate) fn erase_lifetimes(ty: &syn::Type) -> syn::Type {
+    let mut ty = ty.clone();
+    EraseLifetimes.visit_type_mut(&mut ty);
+    ty
+}
+
+struct EraseLifetimes;
+
+impl VisitMut for EraseLifetimes {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        *lifetime = parse_quote!('static);
+    }
+
+    // `for<'a>` introduces its own lifetimes, they can stay
+    fn visit_bound_lifetimes_mut(&mut self, _bound: &mut syn::BoundLifetimes) {}
+
+    fn visit_expr_mut(&mut self, _expr: &mut syn::Expr) {}
+}
+
+struct Substitute<'a>(&'a Placeholders);
+
+impl VisitMut for Substitute<'_> {
+    fn visit_type_path_mut(&mut self, ty: &mut syn::TypePath) {
+        if ty.qself.is_none() {
+            let placeholder = ty.path.get_ident()
+                .and_then(|ident| self.0.params.iter().find(|(param, _)| param == ident))
+                .map(|(_, placeholder)| placeholder);
+
+            if let Some(placeholder) = placeholder {
+                ty.path = parse_quote!(#placeholder);
+                return;
+            }
+        }
+
+        visit_mut::visit_type_path_mut(self, ty);
+    }
+
+    // Discriminants and other expressions are not part of the wire format
+    fn visit_expr_mut(&mut self, _expr: &mut syn::Expr) {}
+}
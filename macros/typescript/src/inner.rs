@@ -1,15 +1,21 @@
-use quote::{format_ident, ToTokens as _};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens as _};
 use syn::{parse_quote, Fields, FieldsNamed, Field, FieldsUnnamed};
 
+use crate::attrs::{parse_docs, parse_serde, Direction, Names, RenameAll, SerdeField, TsField};
+
+/// The last path segment without arguments, `admin::User<T>` is `User`
 fn get_easy_name(typ: &syn::Type) -> String {
-    typ
+    let path: String = typ
         .to_token_stream()
         .to_string()
         .split_whitespace()
         .collect::<String>()
         .chars()
         .take_while(|c| *c != '<')
-        .collect()
+        .collect();
+
+    path.rsplit("::").next().unwrap_or_default().to_string()
 }
 
 fn get_type_name_and_rename_check(typ: &syn::Type, generic_names: &[String]) -> (String, bool) {
@@ -26,17 +32,56 @@ fn get_type_name_and_rename_check(typ: &syn::Type, generic_names: &[String]) ->
 
             (name.clone(), !generic_names.contains(&name))
         },
-        syn::Type::Reference(x) => get_type_name_and_rename_check(&*x.elem, generic_names),
+        // References are named after the referenced type (`&str`), they are never renamed imports
+        syn::Type::Reference(_) => (String::new(), false),
         _ => (String::new(), false),
     }
 }
 
-pub(crate) fn parse_tuple(fields: FieldsUnnamed, holder: syn::Path, generic_names: &[String]) -> syn::Block {
+/// Skipped fields don't have to implement `TypescriptType`, even for the strict derive
+pub(crate) fn skipped_holder() -> syn::Path {
+    parse_quote!(::tsclient::types::TypeHolder)
+}
+
+/// The `#[ts(...)]` overrides as fields of `ComponentReference`
+fn ts_overrides(ts: &TsField) -> TokenStream {
+    let ts_type = match &ts.typ {
+        Some(t) => quote!(Some(String::from(#t))),
+        None => quote!(None),
+    };
+    let ts_name = match &ts.rename {
+        Some(n) => quote!(Some(String::from(#n))),
+        None => quote!(None),
+    };
+    let nullable = ts.nullable.unwrap_or(false);
+    let inline = ts.inline.unwrap_or(false);
+
+    quote! {
+        ts_type: #ts_type,
+        ts_name: #ts_name,
+        nullable: #nullable,
+        inline: #inline,
+    }
+}
+
+/// The doc comment of an item as an `Option<String>` expression
+pub(crate) fn docs_expr(attrs: &[syn::Attribute]) -> TokenStream {
+    match parse_docs(attrs) {
+        Some(docs) => quote!(Some(String::from(#docs))),
+        None => quote!(None),
+    }
+}
+
+pub(crate) fn parse_tuple(fields: FieldsUnnamed, holder: syn::Path, generic_names: &[String], direction: Direction) -> syn::Block {
     let block = fields.unnamed.iter().enumerate().map(|(i, field)| {
         let ident = format_ident!("d{}", i);
         
         let ty = field.ty.clone();
         let (field_type_name, check_rename) = get_type_name_and_rename_check(&field.ty, generic_names);
+        let ts = TsField::parse(&field.attrs);
+        let skip = parse_serde::<SerdeField>(&field.attrs).is_skipped_in(direction) || ts.skip.unwrap_or(false);
+        let holder = if skip || ts.replaces_type() { skipped_holder() } else { holder.clone() };
+        let overrides = ts_overrides(&ts);
         
         parse_quote![{
             let #ident = (&mut &#holder::<#ty>::new()).get_definition(registry);
@@ -50,7 +95,12 @@ pub(crate) fn parse_tuple(fields: FieldsUnnamed, holder: syn::Path, generic_name
 
             let comp = ::tsclient::types::model::ComponentReference {
                 id: #ident,
-                renamed
+                renamed,
+                optional: false,
+                skip: #skip,
+                flatten: false,
+                #overrides
+                docs: None,
             };
 
             __block.push(comp);
@@ -68,6 +118,9 @@ pub(crate) fn parse_tuple(fields: FieldsUnnamed, holder: syn::Path, generic_name
 pub (crate)fn parse_newtype(field: Field, holder: syn::Path, generic_names: &[String]) -> syn::Block {
     let ty = field.ty.clone();
     let (field_type_name, check_rename) = get_type_name_and_rename_check(&field.ty, generic_names);
+    let ts = TsField::parse(&field.attrs);
+    let holder = if ts.replaces_type() { skipped_holder() } else { holder };
+    let overrides = ts_overrides(&ts);
 
     let block = parse_quote!({
         let inner_def = (&mut &#holder::<#ty>::new()).get_definition(registry);
@@ -81,7 +134,12 @@ pub (crate)fn parse_newtype(field: Field, holder: syn::Path, generic_names: &[St
         
         let comp = ::tsclient::types::model::ComponentReference {
             id: inner_def,
-            renamed
+            renamed,
+            optional: false,
+            skip: false,
+            flatten: false,
+            #overrides
+            docs: None,
         };
 
         ::tsclient::types::model::InnerType::NewType(comp)
@@ -90,13 +148,22 @@ pub (crate)fn parse_newtype(field: Field, holder: syn::Path, generic_names: &[St
     return block
 }
 
-pub(crate) fn parse_object(fields: FieldsNamed, holder: syn::Path, generic_names: &[String]) -> syn::Block {
+pub(crate) fn parse_object(fields: FieldsNamed, holder: syn::Path, generic_names: &[String], rename_all: RenameAll, container_default: bool, direction: Direction) -> syn::Block {
     let block = fields.named.iter().enumerate().map(|(i, field)| {
         let ident = format_ident!("d{}", i);
         let ty = field.ty.clone();
         let (field_type_name, check_rename) = get_type_name_and_rename_check(&field.ty, generic_names);
         
-        let field_name = field.ident.clone().map(|f| syn::Member::Named(f)).expect("Parser error named fields").to_token_stream().to_string();
+        let field_att: SerdeField = parse_serde(&field.attrs);
+        let field_ident = field.ident.as_ref().expect("Parser error named fields");
+        let field_name = Names::field(&field_att.rename, rename_all, field_ident).get(direction);
+        let ts = TsField::parse(&field.attrs);
+        let optional = field_att.is_optional_in(direction, container_default, &field.ty) || ts.optional.unwrap_or(false);
+        let skip = field_att.is_skipped_in(direction) || ts.skip.unwrap_or(false);
+        let flatten = field_att.flatten.unwrap_or(false);
+        let holder = if skip || ts.replaces_type() { skipped_holder() } else { holder.clone() };
+        let overrides = ts_overrides(&ts);
+        let docs = docs_expr(&field.attrs);
         parse_quote![{
             let #ident = (&mut &#holder::<#ty>::new()).get_definition(registry);
             let type_name: ::std::string::String = (&mut &#holder::<#ty>::new()).name().split_whitespace().collect();
@@ -109,7 +176,12 @@ pub(crate) fn parse_object(fields: FieldsNamed, holder: syn::Path, generic_names
 
             let comp = ::tsclient::types::model::ComponentReference {
                 id: #ident,
-                renamed
+                renamed,
+                optional: #optional,
+                skip: #skip,
+                flatten: #flatten,
+                #overrides
+                docs: #docs,
             };
 
             __block.push((String::from(#field_name), comp));
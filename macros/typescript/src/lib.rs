@@ -3,26 +3,21 @@ extern crate darling;
 
 use std::collections::HashSet;
 
-use darling::FromMeta;
-use inner::{parse_object, parse_newtype, parse_tuple};
+use attrs::{has_integer_repr, parse_serde, parse_ts, Direction, Names, RenameAll, SerdeContainer, SerdeField, SerdeVariant, TsContainer, TsField, TsVariant};
+use generics::{bounded, erase_lifetimes, erase_lifetimes_data, static_self, Placeholders};
+use inner::{docs_expr, parse_object, parse_newtype, parse_tuple, skipped_holder};
 use proc_macro::{TokenStream};
-use proc_macro2::{TokenTree, Delimiter};
+use proc_macro2::{TokenTree, Delimiter, TokenStream as TokenStream2};
 use proc_macro_error::{abort_call_site, abort, proc_macro_error};
 use syn::{Item as SynItem, parse_macro_input, Attribute, Meta, punctuated::Punctuated, parse::Parser as _, parse_quote, Path, ItemStruct, Index, ItemEnum, Fields, DeriveInput, DataStruct, DataEnum};
 use quote::{quote, ToTokens, format_ident};
 
+mod attrs;
+mod generics;
 mod inner;
 
-
-#[derive(Default, Debug, FromMeta)] 
-struct SerdeEnumTag {
-    tag: Option<String>,
-    content: Option<String>,
-    untagged: Option<bool>,
-}
-
 #[proc_macro_error]
-#[proc_macro_derive(TypeScriptStrict, attributes(serde))]
+#[proc_macro_derive(TypeScriptStrict, attributes(serde, ts))]
 pub fn ts_strict(item: TokenStream,) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
 
@@ -32,7 +27,7 @@ pub fn ts_strict(item: TokenStream,) -> proc_macro::TokenStream {
 }
 
 #[proc_macro_error]
-#[proc_macro_derive(TypeScript, attributes(serde))]
+#[proc_macro_derive(TypeScript, attributes(serde, ts))]
 pub fn ts(item: TokenStream,) -> proc_macro::TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
 
@@ -42,78 +37,264 @@ pub fn ts(item: TokenStream,) -> proc_macro::TokenStream {
 }
 
 fn ts_internal(parse: syn::Data, generics: syn::Generics, ident: syn::Ident, attrs: Vec<Attribute>, holder: syn::Path) -> TokenStream {
+    let ts_att: TsContainer = parse_ts(&attrs);
+    let registration = match ts_att.export {
+        Some(true) => export_registration(&ident, &generics),
+        _ => TokenStream2::new(),
+    };
+
+    let mut output = TokenStream2::from(ts_definition(parse, generics, ident, attrs, holder));
+    output.extend(registration);
+
+    output.into()
+}
+
+/// `#[ts(export)]` adds the type to the list `add_exported_types` registers
+fn export_registration(ident: &syn::Ident, generics: &syn::Generics) -> TokenStream2 {
+    if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+        abort!(ident, "#[ts(export)] is not supported on generic types, add an instantiation with `add_type::<{}<..>>()` instead", ident);
+    }
+    let static_self = static_self(ident, generics);
+
+    quote! {
+        ::tsclient::inventory::submit! {
+            ::tsclient::types::ExportedType::new::<#static_self>()
+        }
+    }
+}
+
+fn ts_definition(parse: syn::Data, generics: syn::Generics, ident: syn::Ident, attrs: Vec<Attribute>, holder: syn::Path) -> TokenStream {
     let mut hashes = Vec::new();
 
-    let serde_att = attrs
-        .iter()
-        .filter(|a| a.path().is_ident("serde"))
-        .map(|attr| SerdeEnumTag::from_meta(&attr.meta) )
-        .find(|x| x.is_ok())
-        .and_then(|x| Some(x.unwrap()));
+    let serde_att: SerdeContainer = parse_serde(&attrs);
+    let ts_att: TsContainer = parse_ts(&attrs);
 
+    if let Some(ts_type) = ts_att.typ {
+        return ts_custom(ts_type, generics, ident);
+    }
+
+    let parse = erase_lifetimes_data(&parse);
 
-    let repr: syn::Expr = match serde_att {
-        Some( SerdeEnumTag { tag: Some(t), content: Some(c), .. }) => {
+    let enum_style: syn::Expr = match ts_att.enum_style.as_deref() {
+        None | Some("union") => parse_quote!(::tsclient::types::model::EnumStyle::Union),
+        Some("enum") => parse_quote!(::tsclient::types::model::EnumStyle::Enum),
+        Some("const") => parse_quote!(::tsclient::types::model::EnumStyle::Const),
+        Some(other) => abort_call_site!("Unknown enum_style {:?}, expected \"union\", \"enum\" or \"const\"", other),
+    };
+
+    // serde ignores `repr`, only `serde_repr` writes the discriminants so it has to be asked for
+    let numeric = match &parse {
+        _ if ts_att.repr != Some(true) => None,
+        syn::Data::Enum(x) if has_integer_repr(&attrs) && generics.params.is_empty() &&
+            x.variants.iter().all(|v| matches!(v.fields, Fields::Unit)) => Some(&ident),
+        _ => abort_call_site!("#[ts(repr)] needs a fieldless enum without generics and with an integer #[repr(..)]"),
+    };
+
+    let repr: syn::Expr = match &serde_att {
+        SerdeContainer { tag: Some(t), content: Some(c), .. } => {
             parse_quote!(::tsclient::types::model::EnumRepresentation::Adjacently(#t.to_string(), #c.to_string()))
         },
-        Some( SerdeEnumTag { tag: Some(t), content: None, .. }) => {
+        SerdeContainer { tag: Some(t), content: None, .. } => {
             parse_quote!(::tsclient::types::model::EnumRepresentation::Internally(#t.to_string()))
         },
-        Some( SerdeEnumTag { tag: None, content: None, untagged: Some(true) }) => {
+        SerdeContainer { tag: None, content: None, untagged: Some(true), .. } => {
             parse_quote!(::tsclient::types::model::EnumRepresentation::Untagged)
         },
         _ => parse_quote!(::tsclient::types::model::EnumRepresentation::Default),
     };
 
-    let gen_inner = generics.params.iter();
-    let generic_names = gen_inner.clone().map(|x| x.to_token_stream().to_string()).collect();
+    let (output_proxy, input_proxy) = proxy_types(&serde_att, &parse);
 
-    let (ident, typ, hash_lit) = match parse {
-        syn::Data::Struct(mut x) => {
-            hashes.extend(parse_hash_of_fields(x.fields.clone(), holder.clone()));
-            (ident.clone(), parse_struct(x, holder, generic_names), "Struct")
-        },
-        syn::Data::Enum(mut x) => {
-            for variant in x.variants.iter() {
-                hashes.extend(parse_hash_of_fields(variant.fields.clone(), holder.clone()));
-            }
+    let brand = ts_att.brand.unwrap_or(false);
+    if brand && !is_newtype(&parse) {
+        abort_call_site!("#[ts(brand)] is only supported on structs with a single unnamed field");
+    }
 
-            (ident.clone(),parse_enum(x, holder, repr, generic_names), "Enum")
-        },
+    // Branded types need their own declaration, `transparent` would otherwise only refer to the field type
+    if let (Some(output), Some(input), false) = (&output_proxy, &input_proxy, brand) {
+        if output.to_token_stream().to_string() == input.to_token_stream().to_string() {
+            return ts_proxy(output.clone(), generics, ident, holder);
+        }
+    }
+
+    let rename_all = RenameAll::from_attr(&serde_att.rename_all);
+    let container_default = serde_att.default.is_some();
+
+    let generic_names: Vec<String> = generics.type_params().map(|x| x.ident.to_string()).collect();
+
+    let hash_lit = match &parse {
+        syn::Data::Struct(_) => "Struct",
+        syn::Data::Enum(_) => "Enum",
         _ => abort_call_site!("Only enums and structs can derive. Unions are not supported"),
     };
 
-    let id_name = ident.to_string();
-    
+    let describe = |data: &syn::Data, proxies: (&Option<syn::Type>, &Option<syn::Type>), generic_names: &[String], direction: Direction| -> syn::Block {
+        let proxy = match direction {
+            Direction::Serialize => proxies.0,
+            Direction::Deserialize => proxies.1,
+        };
+
+        if let Some(proxy) = proxy {
+            let field = match syn::Field::parse_unnamed.parse2(proxy.to_token_stream()) {
+                Ok(field) => field,
+                Err(e) => abort_call_site!("Could not parse proxy type: {}", e),
+            };
+            let newtype = parse_newtype(field, holder.clone(), generic_names);
+            return parse_quote!({ ::tsclient::types::model::Type::Struct(#newtype) });
+        }
+
+        match data {
+            syn::Data::Struct(x) => parse_struct(x.clone(), holder.clone(), generic_names, rename_all, container_default, direction),
+            syn::Data::Enum(x) => parse_enum(x.clone(), holder.clone(), repr.clone(), &serde_att, generic_names, direction, numeric),
+            _ => abort_call_site!("Only enums and structs can derive. Unions are not supported"),
+        }
+    };
+
+    // What the server sends and what it accepts, the latter is only kept when it differs
+    let describe_both = |data: &syn::Data, proxies: (&Option<syn::Type>, &Option<syn::Type>), generic_names: &[String]| -> (syn::Block, syn::Expr) {
+        let typ = describe(data, proxies, generic_names, Direction::Serialize);
+        let input_typ = describe(data, proxies, generic_names, Direction::Deserialize);
+
+        if typ.to_token_stream().to_string() == input_typ.to_token_stream().to_string() {
+            (typ, parse_quote!(None))
+        } else {
+            (typ, parse_quote!(Some(#input_typ)))
+        }
+    };
+
+    hashes.extend(parse_hash_of_data(&parse, (&output_proxy, &input_proxy), holder.clone()));
+
+    let rust_name = ident.to_string();
+    let id_name = ts_att.rename.unwrap_or_else(|| Names::container(&serde_att.rename, &ident).serialize);
+
+    let placeholders = Placeholders::new(&generics);
+    let docs = docs_expr(&attrs);
+    let rust_path = quote!(concat!(module_path!(), "::", #rust_name));
+
+    let (definition, ts_name): (TokenStream2, TokenStream2) = if placeholders.is_empty() {
+        let (typ, input) = describe_both(&parse, (&output_proxy, &input_proxy), &generic_names);
+
+        let definition = quote! {
+            let hash = Self::hash(registry);
+            let typ = #typ;
+            let input = #input;
+
+            let component = ::tsclient::types::model::Component {
+                name: #id_name.to_string(),
+                typ,
+                input,
+                generics: ::std::vec::Vec::new(),
+                docs: #docs,
+                enum_style: #enum_style,
+                brand: #brand,
+                rust_path: Some(String::from(#rust_path)),
+                hash
+            };
+        };
+
+        (definition, quote!(String::from(#id_name)))
+    } else {
+        // One declaration with placeholders for the parameters, every instantiation refers to it
+        let decl_data = placeholders.substitute_data(&parse);
+        let decl_proxies = (
+            output_proxy.as_ref().map(|p| placeholders.substitute(p)),
+            input_proxy.as_ref().map(|p| placeholders.substitute(p)),
+        );
+        let (decl_typ, decl_input) = describe_both(&decl_data, (&decl_proxies.0, &decl_proxies.1), &placeholders.placeholder_names());
+        let decl_hashes = parse_hash_of_data(&decl_data, (&decl_proxies.0, &decl_proxies.1), holder.clone());
+
+        let placeholder_definitions = placeholders.definitions();
+        let param_names = placeholders.ts_names();
+        let params = placeholders.params();
+
+        let definition = quote! {
+            #placeholder_definitions
+
+            #[allow(dead_code)]
+            struct __TsDeclaration;
+
+            let declaration = ::tsclient::types::impls::boilerplate_declaration::<__TsDeclaration>(registry, |registry| {
+                let mut __hash_block = ::std::vec::Vec::new();
+                #(#decl_hashes)*
+
+                let mut __hasher = ::std::collections::hash_map::DefaultHasher::new();
+                __hash_block.sort();
+                <::std::primitive::str as ::std::hash::Hash>::hash(#hash_lit, &mut __hasher);
+                <::std::primitive::str as ::std::hash::Hash>::hash(#id_name, &mut __hasher);
+                <::std::primitive::str as ::std::hash::Hash>::hash(#rust_path, &mut __hasher);
+                <::std::vec::Vec<::std::primitive::u64> as ::std::hash::Hash>::hash(&__hash_block, &mut __hasher);
+
+                let hash = <::std::collections::hash_map::DefaultHasher as ::std::hash::Hasher>::finish(&__hasher);
+                let typ = #decl_typ;
+                let input = #decl_input;
+
+                ::tsclient::types::model::Component {
+                    name: #id_name.to_string(),
+                    typ,
+                    input,
+                    generics: vec![#(String::from(#param_names)),*],
+                    docs: #docs,
+                    enum_style: #enum_style,
+                    brand: #brand,
+                    rust_path: Some(String::from(#rust_path)),
+                    hash
+                }
+            });
+
+            let mut __arguments = ::std::vec::Vec::new();
+            #(
+                __arguments.push(::tsclient::types::model::ComponentReference::new((&mut &#holder::<#params>::new()).get_definition(registry)));
+            )*
+
+            let hash = Self::hash(registry);
+
+            let component = ::tsclient::types::model::Component {
+                name: #id_name.to_string(),
+                typ: ::tsclient::types::model::Type::Generic(::tsclient::types::model::ComponentReference::new(declaration), __arguments),
+                input: None,
+                generics: ::std::vec::Vec::new(),
+                docs: #docs,
+                enum_style: #enum_style,
+                brand: false,
+                rust_path: Some(String::from(#rust_path)),
+                hash
+            };
+        };
+
+        let ts_name = quote! {
+            format!("{}<{}>", #id_name, vec![#((&mut &#holder::<#params>::new()).ts_name(integers)),*].join(", "))
+        };
+
+        (definition, ts_name)
+    };
+
+    let bounded = bounded(&generics);
+    let (impl_generics, ty_generics, where_clause) = bounded.split_for_impl();
+    let static_self = static_self(&ident, &generics);
+
     let output = quote! {
 
-        impl <#(#gen_inner: ::tsclient::types::TypescriptType + 'static,)*> ::tsclient::types::TypescriptType for #ident #generics {
+        impl #impl_generics ::tsclient::types::TypescriptType for #ident #ty_generics #where_clause {
             fn get_definition(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::tsclient::types::builder::HasIndexed {
-                let type_id = ::std::any::TypeId::of::<Self>();
+                let type_id = ::std::any::TypeId::of::<#static_self>();
                 if let Some(existing) = registry.return_existing(type_id) {
                     return existing
                 }
                 registry.start(type_id);
 
-                let hash = Self::hash(registry);
-                let typ = #typ;
-
-                let component = ::tsclient::types::model::Component {
-                    name: #id_name.to_string(),
-                    typ,
-                    hash
-                };
+                #definition
 
                 return registry.finalize(type_id, component)
             }
             fn name() -> ::std::string::String {
-                String::from(#id_name)
+                String::from(#rust_name)
             }
-            fn ts_name() -> ::std::string::String {
-                String::from(#id_name)
+            #[allow(unused_variables)]
+            fn ts_name(integers: ::tsclient::types::model::WideIntegers) -> ::std::string::String {
+                #ts_name
             }
             fn hash(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::std::primitive::u64 {
-                let type_id = ::std::any::TypeId::of::<Self>();
+                let type_id = ::std::any::TypeId::of::<#static_self>();
 
                 if let Some(h) = registry.start_hash(type_id) {
                     return h
@@ -125,6 +306,8 @@ fn ts_internal(parse: syn::Data, generics: syn::Generics, ident: syn::Ident, att
                 let mut __hasher = ::std::collections::hash_map::DefaultHasher::new();
                 __hash_block.sort();
                 <::std::primitive::str as ::std::hash::Hash>::hash(#hash_lit, &mut __hasher);
+                // Types with the same shape are still different types
+                <::std::primitive::str as ::std::hash::Hash>::hash(#rust_path, &mut __hasher);
                 <::std::vec::Vec<::std::primitive::u64> as ::std::hash::Hash>::hash(&__hash_block, &mut __hasher);
 
                 let hash = <::std::collections::hash_map::DefaultHasher as ::std::hash::Hasher>::finish(&__hasher);
@@ -138,39 +321,258 @@ fn ts_internal(parse: syn::Data, generics: syn::Generics, ident: syn::Ident, att
     output.into()
 }
 
+fn is_newtype(data: &syn::Data) -> bool {
+    match data {
+        syn::Data::Struct(DataStruct { fields: Fields::Unnamed(fields), .. }) => fields.unnamed.len() == 1,
+        _ => false,
+    }
+}
+
+/// Types serde writes as something other than an object, an internally tagged newtype variant can't
+/// hold them. Only catches what the type looks like, a newtype struct around a `String` is not known here.
+fn cannot_hold_tag(ty: &syn::Type) -> bool {
+    const NOT_OBJECTS: &[&str] = &[
+        "bool", "char", "str", "String", "f32", "f64",
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "Option", "Vec", "VecDeque", "LinkedList", "BinaryHeap", "HashSet", "BTreeSet",
+    ];
+    const POINTERS: &[&str] = &["Box", "Rc", "Arc", "Cow"];
+
+    match ty {
+        syn::Type::Path(x) if x.qself.is_none() => {
+            let Some(last) = x.path.segments.last() else { return false };
+            let name = last.ident.to_string();
+
+            if POINTERS.contains(&name.as_str()) {
+                let pointee = match &last.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::Type(t) => Some(t),
+                        _ => None,
+                    }),
+                    _ => None,
+                };
+                return pointee.map(cannot_hold_tag).unwrap_or(false)
+            }
+
+            NOT_OBJECTS.contains(&name.as_str())
+        },
+        syn::Type::Tuple(x) => !x.elems.is_empty(),
+        syn::Type::Array(_) |
+        syn::Type::Slice(_) => true,
+        syn::Type::Reference(x) => cannot_hold_tag(&x.elem),
+        syn::Type::Paren(x) => cannot_hold_tag(&x.elem),
+        syn::Type::Group(x) => cannot_hold_tag(&x.elem),
+        _ => false,
+    }
+}
+
+/// Types that serde serializes through another type (`transparent`, `into`, `from`, `try_from`)
+/// are described by that type instead of their own fields. Returns the proxies used
+/// for serializing and deserializing.
+fn proxy_types(serde_att: &SerdeContainer, parse: &syn::Data) -> (Option<syn::Type>, Option<syn::Type>) {
+    let parse_type = |ty: &String| match syn::parse_str::<syn::Type>(ty) {
+        Ok(ty) => erase_lifetimes(&ty),
+        Err(e) => abort_call_site!("Could not parse proxy type {:?}: {}", ty, e),
+    };
+
+    if serde_att.transparent.unwrap_or(false) {
+        let fields = match parse {
+            syn::Data::Struct(x) => &x.fields,
+            _ => abort_call_site!("#[serde(transparent)] is only supported on structs"),
+        };
+
+        let mut remaining = fields.iter().filter(|field| !parse_serde::<SerdeField>(&field.attrs).is_skipped());
+
+        return match (remaining.next(), remaining.next()) {
+            (Some(field), None) => (Some(field.ty.clone()), Some(field.ty.clone())),
+            _ => abort_call_site!("#[serde(transparent)] requires exactly one field that is not skipped"),
+        };
+    }
+
+    let output = serde_att.into.as_ref().map(parse_type);
+    let input = serde_att.from.as_ref().or(serde_att.try_from.as_ref()).map(parse_type);
+
+    if output.is_none() && input.is_none() {
+        return (None, None);
+    }
+
+    // A newtype struct is written as its inner value, so the side without a proxy
+    // can still collapse into the other one
+    let own = match parse {
+        syn::Data::Struct(DataStruct { fields: Fields::Unnamed(x), .. }) if x.unnamed.len() == 1 => Some(x.unnamed[0].ty.clone()),
+        _ => None,
+    };
+
+    (output.or(own.clone()), input.or(own))
+}
+
+fn ts_proxy(proxy: syn::Type, generics: syn::Generics, ident: syn::Ident, holder: syn::Path) -> TokenStream {
+    let bounded = bounded(&generics);
+    let (impl_generics, ty_generics, where_clause) = bounded.split_for_impl();
+    let rust_name = ident.to_string();
+
+    let output = quote! {
+        impl #impl_generics ::tsclient::types::TypescriptType for #ident #ty_generics #where_clause {
+            fn get_definition(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::tsclient::types::builder::HasIndexed {
+                (&mut &#holder::<#proxy>::new()).get_definition(registry)
+            }
+            fn name() -> ::std::string::String {
+                String::from(#rust_name)
+            }
+            fn ts_name(integers: ::tsclient::types::model::WideIntegers) -> ::std::string::String {
+                (&mut &#holder::<#proxy>::new()).ts_name(integers)
+            }
+            fn hash(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::std::primitive::u64 {
+                (&mut &#holder::<#proxy>::new()).hash(registry)
+            }
+        }
+    };
+
+    output.into()
+}
+
+/// `#[ts(type = "...")]` replaces the whole type with the given TypeScript type
+fn ts_custom(ts_type: String, generics: syn::Generics, ident: syn::Ident) -> TokenStream {
+    let mut bounded = generics.clone();
+    for param in bounded.type_params_mut() {
+        param.bounds.push(parse_quote!('static));
+    }
+    let (impl_generics, ty_generics, where_clause) = bounded.split_for_impl();
+    let static_self = static_self(&ident, &generics);
+    let rust_name = ident.to_string();
+
+    let output = quote! {
+        impl #impl_generics ::tsclient::types::TypescriptType for #ident #ty_generics #where_clause {
+            fn get_definition(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::tsclient::types::builder::HasIndexed {
+                ::tsclient::types::impls::boilerplate_simple_definition::<#static_self>(::tsclient::types::model::Type::SimpleType(String::from(#ts_type)), registry)
+            }
+            fn name() -> ::std::string::String {
+                String::from(#rust_name)
+            }
+            fn ts_name(_: ::tsclient::types::model::WideIntegers) -> ::std::string::String {
+                String::from(#ts_type)
+            }
+            fn hash(registry: &mut ::tsclient::types::builder::GlobalTypeRegistry) -> ::std::primitive::u64 {
+                ::tsclient::types::impls::boilerplate_simple_hash::<#static_self>(registry)
+            }
+        }
+    };
+
+    output.into()
+}
+
+fn parse_hash_of_data(data: &syn::Data, proxies: (&Option<syn::Type>, &Option<syn::Type>), holder: syn::Path) -> Vec<syn::Block> {
+    let mut hashes = Vec::new();
+
+    match data {
+        syn::Data::Struct(x) => hashes.extend(parse_hash_of_fields(x.fields.clone(), holder.clone())),
+        syn::Data::Enum(x) => {
+            for variant in x.variants.iter().filter(|v| !parse_serde::<SerdeVariant>(&v.attrs).is_skipped()) {
+                hashes.extend(parse_hash_of_fields(variant.fields.clone(), holder.clone()));
+            }
+        },
+        _ => {},
+    }
+
+    for proxy in proxies.0.iter().chain(proxies.1.iter()) {
+        hashes.push(parse_quote![{
+            __hash_block.push((&mut &#holder::<#proxy>::new()).hash(registry));
+        }]);
+    }
+
+    hashes
+}
+
 fn parse_hash_of_fields(fields: Fields, holder: syn::Path) -> Vec<syn::Block> {
-    let res = fields.iter().enumerate().map(|(i, field)| {
+    let res = fields.iter().filter(|field| !parse_serde::<SerdeField>(&field.attrs).is_skipped()).filter_map(|field| {
         let ty = field.ty.clone();
-        parse_quote![{
-            let field_hash = (&mut &#holder::<#ty>::new()).hash(registry);
+        let ts = TsField::parse(&field.attrs);
+
+        match (ts.skip.unwrap_or(false), ts.typ) {
+            (true, _) => None,
+            (false, Some(ts_type)) => Some(parse_quote![{
+                let mut __field_hasher = ::std::collections::hash_map::DefaultHasher::new();
+                <::std::primitive::str as ::std::hash::Hash>::hash(#ts_type, &mut __field_hasher);
+
+                __hash_block.push(<::std::collections::hash_map::DefaultHasher as ::std::hash::Hasher>::finish(&__field_hasher));
+            }]),
+            (false, None) => Some(parse_quote![{
+                let field_hash = (&mut &#holder::<#ty>::new()).hash(registry);
 
-            __hash_block.push(field_hash);
-        }]
+                __hash_block.push(field_hash);
+            }]),
+        }
     }).collect::<Vec<syn::Block>>();
 
     return res;
 }
 
-fn parse_enum(item: DataEnum, holder: syn::Path, repr: syn::Expr, generic_names: Vec<String>) -> syn::Block { 
+/// `numeric` is the enum itself when its variants are written as their discriminant
+fn parse_enum(item: DataEnum, holder: syn::Path, repr: syn::Expr, serde_att: &SerdeContainer, generic_names: &[String], direction: Direction, numeric: Option<&syn::Ident>) -> syn::Block { 
     let mut blocks: Vec<syn::Block> = Vec::new();
 
+    let rename_all = RenameAll::from_attr(&serde_att.rename_all);
+    let rename_all_fields = RenameAll::from_attr(&serde_att.rename_all_fields);
+    let internally_tagged = serde_att.tag.is_some() && serde_att.content.is_none();
+
     for variant in item.variants {
-        let ident = variant.ident.to_string();
+        let variant_att: SerdeVariant = parse_serde(&variant.attrs);
+        let ident = Names::variant(&variant_att.rename, rename_all, &variant.ident).get(direction);
+        let field_rename_all = RenameAll::from_attr(&variant_att.rename_all).or(rename_all_fields);
+        let untagged = variant_att.untagged.unwrap_or(false);
+        // Unknown tags only end up in the `other` variant when reading
+        let other = variant_att.other.unwrap_or(false) && direction == Direction::Deserialize;
+        let skip = variant_att.is_skipped_in(direction);
+        let holder = if skip { skipped_holder() } else { holder.clone() };
+
+        if internally_tagged && !untagged && matches!(&variant.fields, syn::Fields::Unnamed(x) if x.unnamed.len() > 1) {
+            abort!(variant.ident, "Tuple variants are not supported by internally tagged enums (#[serde(tag = \"...\")])");
+        }
+
+        if internally_tagged && !untagged {
+            if let syn::Fields::Unnamed(x) = &variant.fields {
+                if let Some(field) = x.unnamed.iter().next().filter(|f| cannot_hold_tag(&f.ty)) {
+                    abort!(field.ty, "Newtype variants of internally tagged enums (#[serde(tag = \"...\")]) have to hold a struct or a map, serde can't add the tag to this type");
+                }
+            }
+        }
 
         let parsed = match variant.fields {
-            syn::Fields::Named(named) => parse_object(named, holder.clone(), &generic_names),
+            syn::Fields::Named(named) => parse_object(named, holder, generic_names, field_rename_all, false, direction),
             syn::Fields::Unnamed(unnamed) => match unnamed.unnamed.len() {
                 0 => parse_quote!({::tsclient::types::model::InnerType::SimpleVariant(#ident.to_string())}),
-                1 => parse_newtype(unnamed.unnamed[0].clone(), holder.clone(), &generic_names),
-                _ => parse_tuple(unnamed, holder.clone(), &generic_names)
+                1 => parse_newtype(unnamed.unnamed[0].clone(), holder, generic_names),
+                _ => parse_tuple(unnamed, holder, generic_names, direction)
             },
             syn::Fields::Unit => parse_quote!({::tsclient::types::model::InnerType::SimpleVariant(#ident.to_string())})
         };
 
+        let docs = docs_expr(&variant.attrs);
+        let label = match parse_ts::<TsVariant>(&variant.attrs).label {
+            Some(label) => quote!(Some(String::from(#label))),
+            None => quote!(None),
+        };
+        let discriminant = match numeric {
+            Some(enum_ident) => {
+                let variant_ident = &variant.ident;
+                quote!(Some(#enum_ident::#variant_ident as i64))
+            },
+            None => quote!(None),
+        };
+
         blocks.push(parse_quote!({
             let inner = #parsed;
 
-            __block.push((#ident.to_string(), inner));
+            __block.push(::tsclient::types::model::EnumVariant {
+                name: #ident.to_string(),
+                inner,
+                untagged: #untagged,
+                other: #other,
+                skip: #skip,
+                docs: #docs,
+                label: #label,
+                discriminant: #discriminant,
+            });
         }));
     }
 
@@ -182,13 +584,13 @@ fn parse_enum(item: DataEnum, holder: syn::Path, repr: syn::Expr, generic_names:
     })
 }
 
-fn parse_struct(item: DataStruct, holder: syn::Path, generic_names: Vec<String>) -> syn::Block { 
+fn parse_struct(item: DataStruct, holder: syn::Path, generic_names: &[String], rename_all: RenameAll, container_default: bool, direction: Direction) -> syn::Block { 
     let inner_typ = match item.fields {
-        syn::Fields::Named(named) => parse_object(named, holder, &generic_names),
+        syn::Fields::Named(named) => parse_object(named, holder, generic_names, rename_all, container_default, direction),
         syn::Fields::Unnamed(unnamed) => match unnamed.unnamed.len() {
             0 => todo!(),
-            1 => parse_newtype(unnamed.unnamed[0].clone(), holder, &generic_names),
-            _ => parse_tuple(unnamed, holder, &generic_names)
+            1 => parse_newtype(unnamed.unnamed[0].clone(), holder, generic_names),
+            _ => parse_tuple(unnamed, holder, generic_names, direction)
         }
         syn::Fields::Unit => parse_quote!(::tsclient::types::model::InnerType::Null),
         
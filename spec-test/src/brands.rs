@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[ts(brand)]
+pub struct UserId(pub u32);
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[serde(transparent)]
+#[ts(brand)]
+pub struct OrderId(pub String);
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[ts(brand)]
+pub struct Tagged<T>(pub T);
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Quantity(pub u32);
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Order {
+    pub id: OrderId,
+    pub customer: UserId,
+    pub quantity: Quantity,
+    pub note: Tagged<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dto_imports, render_dto};
+
+    #[test]
+    fn branded_alias_with_constructor() {
+        let content = render_dto::<UserId>("UserId");
+
+        assert!(content.contains("export type UserId = number & { readonly __brand: \"UserId\" }"), "{}", content);
+        assert!(content.contains("export const UserId = (value: number): UserId => value as UserId"), "{}", content);
+    }
+
+    #[test]
+    fn transparent_newtypes_can_be_branded() {
+        let content = render_dto::<OrderId>("OrderId");
+
+        assert!(content.contains("export type OrderId = string & { readonly __brand: \"OrderId\" }"), "{}", content);
+        assert!(render_dto::<Order>("Order").contains("\tid: OrderId;"));
+        assert!(dto_imports::<Order>("Order").contains(&String::from("OrderId")));
+    }
+
+    #[test]
+    fn generic_brand() {
+        let content = render_dto::<Order>("Tagged");
+
+        assert!(content.contains("export type Tagged<T> = T & { readonly __brand: \"Tagged\" }"), "{}", content);
+        assert!(content.contains("export const Tagged = <T>(value: T): Tagged<T> => value as Tagged<T>"), "{}", content);
+    }
+
+    #[test]
+    fn newtypes_are_not_branded_by_default() {
+        let content = render_dto::<Quantity>("Quantity");
+
+        assert!(content.contains("export type Quantity = number"), "{}", content);
+        assert!(!content.contains("__brand"), "{}", content);
+    }
+}
@@ -0,0 +1,87 @@
+use axum::{extract::Query, Json};
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Invoice {
+    pub number: u32,
+    pub total: f64,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct InvoiceFilter {
+    pub paid: Option<bool>,
+}
+
+pub async fn list_invoices(Query(_filter): Query<InvoiceFilter>) -> Json<Vec<Invoice>> {
+    Json(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::{Path, PathBuf}};
+
+    use tsclient::{ExportDiff, ExportOptions, axum::{routing::get, Router}};
+
+    use super::*;
+    use crate::temp_dir;
+
+    fn router() -> Router<()> {
+        Router::<()>::new().route("/api/invoices", get(list_invoices))
+    }
+
+    fn exported(name: &str, options: &ExportOptions) -> PathBuf {
+        let path = temp_dir(name);
+        router().api.export(&path, options).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn fresh_export_passes() {
+        let options = ExportOptions::new().remove_prefix("/api");
+        let path = exported("check-fresh", &options);
+
+        router().api.check(&path, &options).unwrap();
+    }
+
+    #[test]
+    fn reports_changed_missing_and_extra_files() {
+        let options = ExportOptions::new().remove_prefix("/api");
+        let path = exported("check-stale", &options);
+
+        let invoice = path.join("dto/Invoice.ts");
+        fs::write(&invoice, fs::read_to_string(&invoice).unwrap().replace("total: number", "total: string")).unwrap();
+        fs::remove_file(path.join("dto/InvoiceFilter.ts")).unwrap();
+        // A route that was removed since the last export, the hand-written file is not flagged
+        fs::write(path.join("api/old.ts"), "").unwrap();
+        let manifest = path.join(".tsclient-manifest");
+        fs::write(&manifest, fs::read_to_string(&manifest).unwrap() + "api/old.ts\n").unwrap();
+        fs::write(path.join("api/notes.ts"), "").unwrap();
+
+        let diff = router().api.diff(&path, &options).unwrap();
+        assert_eq!(diff.missing, [PathBuf::from("dto/InvoiceFilter.ts")]);
+        assert_eq!(diff.extra, [PathBuf::from("api/old.ts")]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].path, Path::new("dto/Invoice.ts"));
+        assert_eq!(diff.changed[0].expected.trim(), "total: number");
+        assert_eq!(diff.changed[0].found.trim(), "total: string");
+
+        let error = router().api.check(&path, &options).unwrap_err();
+        assert!(error.downcast_ref::<ExportDiff>().is_some());
+        assert!(error.to_string().contains("changed: dto/Invoice.ts"), "{}", error);
+    }
+
+    #[test]
+    fn nothing_exported_yet() {
+        let options = ExportOptions::new();
+        let empty = temp_dir("check-empty");
+
+        for path in [empty.join("missing"), empty] {
+            let diff = router().api.diff(&path, &options).unwrap();
+
+            assert!(diff.missing.contains(&PathBuf::from("dto/Invoice.ts")), "{}", path.display());
+            assert!(diff.changed.is_empty() && diff.extra.is_empty(), "{}", path.display());
+        }
+    }
+}
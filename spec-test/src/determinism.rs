@@ -0,0 +1,102 @@
+use axum::{extract::Query, Json};
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Order {
+    pub id: u32,
+    pub customer: Customer,
+    pub lines: Vec<OrderLine>,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Customer {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct OrderLine {
+    pub product: String,
+    pub quantity: u32,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct OrderFilter {
+    pub customer: Option<String>,
+}
+
+pub async fn list_orders(Query(_filter): Query<OrderFilter>) -> Json<Vec<Order>> {
+    Json(Vec::new())
+}
+
+pub async fn create_order(Json(order): Json<Order>) -> Json<Order> {
+    Json(order)
+}
+
+pub async fn delete_order(Query(_filter): Query<OrderFilter>) -> Json<()> {
+    Json(())
+}
+
+pub async fn get_customer(Query(_filter): Query<OrderFilter>) -> Json<Customer> {
+    Json(Customer { name: String::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, fs, path::{Path, PathBuf}};
+
+    use tsclient::axum::{routing::get, Router};
+
+    use super::*;
+
+    fn router() -> Router<()> {
+        Router::<()>::new()
+            .route("/api/orders", get(list_orders).post(create_order).delete(delete_order))
+            .route("/api/zebra/customer", get(get_customer))
+            .route("/api/alpha/customer", get(get_customer))
+    }
+
+    fn read_dir(root: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+        let mut files = BTreeMap::new();
+        let mut dirs = vec![root.to_path_buf()];
+
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    files.insert(path.strip_prefix(root).unwrap().to_path_buf(), fs::read(&path).unwrap());
+                }
+            }
+        }
+
+        files
+    }
+
+    #[test]
+    fn exports_are_byte_identical() {
+        let first = crate::temp_dir("determinism-first");
+        let second = crate::temp_dir("determinism-second");
+
+        router().api.export_to(&first, Some("/api")).unwrap();
+        router().api.export_to(&second, Some("/api")).unwrap();
+
+        let first_files = read_dir(&first);
+        assert!(first_files.contains_key(Path::new("api/orders.ts")), "{:?}", first_files.keys());
+        assert!(first_files == read_dir(&second));
+
+        let client = String::from_utf8(first_files[Path::new("api/client.ts")].clone()).unwrap();
+        let orders = client.find("orders:").unwrap();
+        let zebra = client.find("zebra:").unwrap();
+        let alpha = client.find("alpha:").unwrap();
+        assert!(orders < zebra && zebra < alpha, "Routes are not in declaration order\n{}", client);
+
+        let routes = String::from_utf8(first_files[Path::new("api/orders.ts")].clone()).unwrap();
+        let get = routes.find("function getOrders(").unwrap();
+        let post = routes.find("function postOrders(").unwrap();
+        let delete = routes.find("function deleteOrders(").unwrap();
+        assert!(get < post && post < delete, "{}", routes);
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct User {
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    #[serde(rename(serialize = "userName", deserialize = "username"))]
+    pub name: String,
+    pub address: Address,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Team {
+    pub owner: User,
+    pub members: Vec<User>,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[serde(tag = "type")]
+pub enum Shape {
+    Circle { radius: f64 },
+    #[serde(skip_deserializing)]
+    Computed { area: f64 },
+    #[serde(other)]
+    Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{render_dto, rendered_files};
+
+    #[test]
+    fn asymmetric_fields_get_an_input_type() {
+        let output = render_dto::<User>("User");
+
+        assert!(output.contains("\tid: number;"), "{}", output);
+        assert!(output.contains("\tuserName: string;"), "{}", output);
+        assert!(output.contains("\taddress: Address"), "{}", output);
+
+        let input = render_dto::<User>("UserInput");
+
+        assert!(!input.contains("id"), "{}", input);
+        assert!(input.contains("\tusername: string;"), "{}", input);
+        assert!(input.contains("\taddress: Address"), "{}", input);
+
+        let json = serde_json::to_string(&User {
+            id: 1,
+            name: String::from("ferris"),
+            address: Address { street: String::new(), city: String::new() },
+        }).unwrap();
+        assert!(json.starts_with(r#"{"id":1,"userName":"ferris""#), "{}", json);
+    }
+
+    #[test]
+    fn symmetric_types_are_shared() {
+        let files = rendered_files::<User>();
+
+        assert!(files.contains(&String::from("Address")), "{:?}", files);
+        assert!(!files.contains(&String::from("AddressInput")), "{:?}", files);
+    }
+
+    #[test]
+    fn containers_of_asymmetric_types_get_an_input_type() {
+        let output = render_dto::<Team>("Team");
+
+        assert!(output.contains("\towner: User;"), "{}", output);
+        assert!(output.contains("\tmembers: UserArray"), "{}", output);
+
+        let input = render_dto::<Team>("TeamInput");
+
+        assert!(input.contains("\towner: UserInput;"), "{}", input);
+        assert!(input.contains("\tmembers: UserInputArray"), "{}", input);
+    }
+
+    #[test]
+    fn enum_variants_differ_by_direction() {
+        let output = render_dto::<Shape>("Shape");
+
+        assert!(output.contains("\"Computed\""), "{}", output);
+        assert!(output.contains("\ttype: \"Unknown\";"), "{}", output);
+
+        let input = render_dto::<Shape>("ShapeInput");
+
+        assert!(!input.contains("Computed"), "{}", input);
+        assert!(input.contains("\ttype: string;"), "{}", input);
+
+        let unknown: Shape = serde_json::from_str(r#"{"type":"Square"}"#).unwrap();
+        assert!(matches!(unknown, Shape::Unknown));
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+/// A registered customer
+///
+/// Customers are created by the sign up flow.
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Customer {
+    /// The public id
+    pub id: i64,
+    pub name: String,
+    /// Ends with */ on purpose
+    pub note: String,
+}
+
+/// How the order is paid
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[serde(tag = "type")]
+pub enum Payment {
+    /// Paid on delivery
+    Cash,
+    Card {
+        /// The last four digits
+        last_digits: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn type_docs_become_jsdoc_blocks() {
+        let content = render_dto::<Customer>("Customer");
+
+        assert!(content.contains("/**\n * A registered customer\n *\n * Customers are created by the sign up flow.\n */\nexport type Customer = {"), "{}", content);
+    }
+
+    #[test]
+    fn field_docs_are_written_above_the_property() {
+        let content = render_dto::<Customer>("Customer");
+
+        assert!(content.contains("\n\t/** The public id */\n\tid: number;"), "{}", content);
+        assert!(content.contains(";\n\tname: string;"), "{}", content);
+        assert!(content.contains("/** Ends with *\\/ on purpose */"), "{}", content);
+    }
+
+    #[test]
+    fn variant_docs_are_written_above_the_variant() {
+        let content = render_dto::<Payment>("Payment");
+
+        assert!(content.contains("/** Paid on delivery */\nexport type Cash = {"), "{}", content);
+        assert!(content.contains("\t/** The last four digits */\n\tlast_digits: string"), "{}", content);
+        assert!(content.contains("/** How the order is paid */\nexport type Payment = Cash | Card"), "{}", content);
+    }
+}
@@ -0,0 +1,92 @@
+use bigdecimal::BigDecimal;
+use bytes::Bytes;
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use indexmap::{IndexMap, IndexSet};
+use ordered_float::OrderedFloat;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use smol_str::SmolStr;
+use time::OffsetDateTime;
+use tsclient::{TypeScriptStrict, prelude::*};
+use url::Url;
+use uuid::Uuid;
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Upload {
+    pub id: Uuid,
+    pub uploaded_at: OffsetDateTime,
+    pub local_time: NaiveDateTime,
+    pub reminder: NaiveTime,
+    pub content: Bytes,
+    pub headers: IndexMap<String, String>,
+    pub tags: IndexSet<String>,
+    pub score: OrderedFloat<f64>,
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Invoice {
+    pub total: Decimal,
+    pub exact_total: BigDecimal,
+    pub link: Url,
+    pub code: SmolStr,
+    pub due_in: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn ecosystem_types_follow_their_serde_format() {
+        let content = render_dto::<Upload>("Upload");
+
+        assert!(content.contains("\tid: string;"), "{}", content);
+        assert!(content.contains("\tuploaded_at: [number, number, number, number, number, number, number, number, number];"), "{}", content);
+        assert!(content.contains("\tlocal_time: string;"), "{}", content);
+        assert!(content.contains("\treminder: string;"), "{}", content);
+        assert!(content.contains("\tcontent: Array<number>;"), "{}", content);
+        assert!(content.contains("\theaders: Record<string, string>;"), "{}", content);
+        assert!(content.contains("\ttags: Array<string>;"), "{}", content);
+        assert!(content.contains("\tscore: number"), "{}", content);
+
+        let json = serde_json::to_value(&Upload {
+            id: Uuid::nil(),
+            uploaded_at: OffsetDateTime::UNIX_EPOCH,
+            local_time: NaiveDateTime::default(),
+            reminder: NaiveTime::default(),
+            content: Bytes::from_static(&[1, 2]),
+            headers: IndexMap::new(),
+            tags: IndexSet::new(),
+            score: OrderedFloat(1.5),
+        }).unwrap();
+        assert!(json["id"].is_string());
+        assert_eq!(json["uploaded_at"], serde_json::json!([1970, 1, 0, 0, 0, 0, 0, 0, 0]));
+        assert!(json["content"].is_array());
+        assert!(json["score"].is_number());
+    }
+
+    #[test]
+    fn optional_ecosystem_types_follow_their_serde_format() {
+        let content = render_dto::<Invoice>("Invoice");
+
+        assert!(content.contains("\ttotal: string;"), "{}", content);
+        assert!(content.contains("\texact_total: string;"), "{}", content);
+        assert!(content.contains("\tlink: string;"), "{}", content);
+        assert!(content.contains("\tcode: string;"), "{}", content);
+        assert!(content.contains("\tdue_in: [number, number]"), "{}", content);
+
+        let json = serde_json::to_value(&Invoice {
+            total: Decimal::new(150, 2),
+            exact_total: BigDecimal::from(3),
+            link: Url::parse("https://example.com").unwrap(),
+            code: SmolStr::new("A1"),
+            due_in: Duration::seconds(90),
+        }).unwrap();
+        assert!(json["total"].is_string());
+        assert!(json["exact_total"].is_string());
+        assert!(json["link"].is_string());
+        assert!(json["code"].is_string());
+        assert_eq!(json["due_in"], serde_json::json!([90, 0]));
+    }
+}
@@ -0,0 +1,102 @@
+use axum::{extract::Query, Json};
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Product {
+    pub name: String,
+    pub price: u32,
+    pub stock: u64,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct ProductFilter {
+    pub name: Option<String>,
+}
+
+pub async fn list_products(Query(_filter): Query<ProductFilter>) -> Json<Vec<Product>> {
+    Json(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::{Path, PathBuf}};
+
+    use tsclient::{ExportOptions, MethodNaming, TypeNaming, WideIntegers, axum::{routing::get, Router}};
+
+    use super::*;
+    use crate::temp_dir;
+
+    fn export(name: &str, options: &ExportOptions) -> PathBuf {
+        let path = temp_dir(name);
+
+        Router::<()>::new()
+            .route("/api/shop/products", get(list_products))
+            .api.export(&path, options).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn defaults_match_export_to() {
+        let path = export("options-default", &ExportOptions::new().remove_prefix("/api"));
+
+        let routes = fs::read_to_string(path.join("api/shop/products.ts")).unwrap();
+        assert!(routes.starts_with("/** \n * This File was generated automagically"), "{}", routes);
+        assert!(routes.contains("export async function getProducts_RAW("), "{}", routes);
+        assert!(routes.contains("from \"../../dto/Product\""), "{}", routes);
+        assert!(fs::read_to_string(path.join("api/client.ts")).unwrap().contains("class Client {"));
+    }
+
+    #[test]
+    fn custom_options() {
+        let options = ExportOptions::new()
+            .remove_prefix("/api")
+            .dto_dir("types")
+            .api_dir("endpoints")
+            .header("// generated\n")
+            .indent("  ")
+            .client_class("Backend")
+            .method_naming(MethodNaming::SnakeCase)
+            .raw_functions(false);
+        let path = export("options-custom", &options);
+
+        let product = fs::read_to_string(path.join("types/Product.ts")).unwrap();
+        assert!(product.starts_with("// generated\n"), "{}", product);
+        assert!(product.contains("\n  name: string;"), "{}", product);
+        assert!(!product.contains('\t'), "{}", product);
+
+        let routes = fs::read_to_string(path.join("endpoints/shop/products.ts")).unwrap();
+        assert!(routes.contains("\nasync function get_products_RAW("), "{}", routes);
+        assert!(routes.contains("export async function get_products("), "{}", routes);
+        assert!(routes.contains("from \"../../types/Product\""), "{}", routes);
+
+        let client = fs::read_to_string(path.join("endpoints/client.ts")).unwrap();
+        assert!(client.contains("class Backend {\n  BASE_PATH"), "{}", client);
+        assert!(client.contains("new Backend()"), "{}", client);
+    }
+
+    #[test]
+    fn types_only() {
+        let path = export("options-types-only", &ExportOptions::new().client(false));
+
+        assert!(path.join("dto/Product.ts").exists());
+        assert!(!path.join("api").exists());
+    }
+
+    #[test]
+    fn one_router_with_different_options() {
+        let router = Router::<()>::new().route("/api/shop/products", get(list_products));
+
+        let numbers = router.api.render(&ExportOptions::new()).unwrap();
+        let strings = router.api.render(&ExportOptions::new().wide_integers(WideIntegers::String).type_naming(TypeNaming::Folders)).unwrap();
+
+        let product = &numbers.files[Path::new("dto/Product.ts")];
+        assert!(product.contains("\tstock: number"), "{}", product);
+        assert!(numbers.warnings.iter().any(|w| w.contains("`u64`")), "{:?}", numbers.warnings);
+
+        let product = &strings.files[Path::new("dto/export_options/Product.ts")];
+        assert!(product.contains("\tstock: string"), "{}", product);
+        assert!(strings.warnings.is_empty(), "{:?}", strings.warnings);
+    }
+}
@@ -0,0 +1,99 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+/// Sent over a WebSocket, no handler refers to it
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[ts(export)]
+pub struct ChatEvent {
+    pub author: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct StoredSettings {
+    pub theme: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Envelope<T> {
+    pub version: u32,
+    pub data: T,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Status {
+    pub online: bool,
+}
+
+pub async fn get_status() -> Json<Status> {
+    Json(Status { online: true })
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::{axum::{routing::get, Router}, types::{builder::TypeBuilder, ExportedType}};
+
+    use super::*;
+
+    fn exported(router: &Router<()>) -> Vec<String> {
+        let registry = router.api.components.lock().unwrap();
+
+        TypeBuilder::build(&registry).file_map.keys().cloned().collect()
+    }
+
+    #[test]
+    fn export_attribute_registers_without_a_route() {
+        let types = exported(&Router::<()>::new().route("/status", get(get_status)).add_exported_types());
+
+        assert!(types.contains(&String::from("ChatEvent")), "{:?}", types);
+        assert!(types.contains(&String::from("Status")), "{:?}", types);
+        assert!(!types.contains(&String::from("StoredSettings")), "{:?}", types);
+    }
+
+    #[test]
+    fn exported_types_are_opt_in() {
+        let types = exported(&Router::<()>::new().route("/status", get(get_status)));
+
+        assert!(!types.contains(&String::from("ChatEvent")), "{:?}", types);
+        assert!(types.contains(&String::from("Status")), "{:?}", types);
+    }
+
+    #[test]
+    fn exported_types_are_sorted() {
+        let paths: Vec<&str> = ExportedType::all().iter().map(|exported| (exported.path)()).collect();
+        let mut sorted = paths.clone();
+        sorted.sort();
+
+        assert_eq!(paths, sorted);
+        assert!(paths.contains(&"spec_test::exported::ChatEvent"), "{:?}", paths);
+    }
+
+    #[test]
+    fn exported_types_survive_nesting() {
+        let nested = Router::<()>::new().add_exported_types();
+        let types = exported(&Router::<()>::new().nest("/chat", nested));
+
+        assert!(types.contains(&String::from("ChatEvent")), "{:?}", types);
+    }
+
+    #[test]
+    fn add_type() {
+        let router = Router::<()>::new()
+            .route("/status", get(get_status))
+            .add_type::<StoredSettings>()
+            .add_type::<Envelope<StoredSettings>>();
+        let types = exported(&router);
+
+        assert!(types.contains(&String::from("StoredSettings")), "{:?}", types);
+        assert!(types.contains(&String::from("Envelope")), "{:?}", types);
+    }
+
+    #[test]
+    fn added_types_survive_nesting() {
+        let nested = Router::<()>::new().add_type::<StoredSettings>();
+        let types = exported(&Router::<()>::new().nest("/settings", nested));
+
+        assert!(types.contains(&String::from("StoredSettings")), "{:?}", types);
+    }
+}
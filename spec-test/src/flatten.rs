@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScript, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub struct Pagination {
+    pub page: u32,
+    pub per_page: u32,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub struct UserPage {
+    pub users: Vec<String>,
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub struct Extensible {
+    pub id: i32,
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub struct OnlyFlattened {
+    #[serde(flatten)]
+    pub pagination: Pagination,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub struct MaybePaginated {
+    pub users: Vec<String>,
+    #[serde(flatten)]
+    pub pagination: Option<Pagination>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn flattened_struct_becomes_intersection() {
+        let content = render_dto::<UserPage>("UserPage");
+        assert!(content.contains("export type UserPage = {\n\tusers: Array<string>\n} & Pagination"), "{}", content);
+
+        let content = render_dto::<OnlyFlattened>("OnlyFlattened");
+        assert!(content.contains("export type OnlyFlattened = Pagination"), "{}", content);
+    }
+
+    #[test]
+    fn flattened_map_becomes_index_signature() {
+        let content = render_dto::<Extensible>("Extensible");
+        assert!(content.contains("} & { [key: string]: string }"), "{}", content);
+    }
+
+    #[test]
+    fn flattened_option_makes_the_fields_optional() {
+        let content = render_dto::<MaybePaginated>("MaybePaginated");
+        assert!(content.contains("export type MaybePaginated = {\n\tusers: Array<string>\n} & Partial<Pagination>"), "{}", content);
+
+        let json = serde_json::to_string(&MaybePaginated { users: Vec::new(), pagination: None }).unwrap();
+        assert_eq!(json, r#"{"users":[]}"#);
+    }
+}
@@ -0,0 +1,96 @@
+use serde::Serialize;
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Borrowed<'a> {
+    pub name: &'a str,
+    pub tags: Vec<&'a str>,
+}
+
+#[derive(TypeScriptStrict)]
+pub struct Buffer<const N: usize> {
+    pub data: [u8; N],
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Labeled<'a, T: Clone>
+where
+    T: PartialEq,
+{
+    pub label: &'a str,
+    pub value: T,
+}
+
+pub trait Marker {}
+
+impl Marker for i32 {}
+
+/// Describing `Outer` describes `Inner` with the parameter of `Outer`, it has to satisfy the bounds of both
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Inner<T: Marker + Serialize> {
+    pub value: T,
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Outer<T>
+where
+    T: Marker + Serialize + serde::de::DeserializeOwned + Ord + std::fmt::Display,
+{
+    pub inner: Inner<T>,
+    pub values: Vec<Inner<T>>,
+}
+
+#[derive(TypeScriptStrict)]
+pub struct Inventory<'a> {
+    pub borrowed: Borrowed<'a>,
+    pub buffer: Buffer<4>,
+    pub labeled: Labeled<'a, i32>,
+    pub outer: Outer<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::WideIntegers;
+
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn lifetimes_are_described_like_owned_types() {
+        let content = render_dto::<Inventory>("Borrowed");
+
+        assert!(content.contains("\tname: string;"), "{}", content);
+        assert!(content.contains("\ttags: Array<string>"), "{}", content);
+    }
+
+    #[test]
+    fn const_generics_are_kept_out_of_typescript() {
+        let content = render_dto::<Inventory>("Buffer");
+
+        assert!(content.contains("export type Buffer = {"), "{}", content);
+        assert!(content.contains("\tdata: Array<number>"), "{}", content);
+    }
+
+    #[test]
+    fn bounded_type_parameters_stay_generic() {
+        let content = render_dto::<Inventory>("Labeled");
+
+        assert!(content.contains("export type Labeled<T> = {"), "{}", content);
+        assert!(content.contains("\tlabel: string;"), "{}", content);
+
+        let content = render_dto::<Inventory>("Inventory");
+
+        assert!(content.contains("\tlabeled: Labeled<number>"), "{}", content);
+        assert_eq!(Labeled::<'static, i32>::ts_name(WideIntegers::Number), "Labeled<number>");
+    }
+
+    #[test]
+    fn bounded_generics_refer_to_bounded_generics() {
+        let content = render_dto::<Inventory>("Outer");
+
+        assert!(content.contains("export type Outer<T> = {"), "{}", content);
+        assert!(content.contains("\tinner: Inner<T>;"), "{}", content);
+        assert!(content.contains("\tvalues: Array<Inner<T>>"), "{}", content);
+        assert!(render_dto::<Inventory>("Inventory").contains("\touter: Outer<number>"));
+    }
+}
@@ -0,0 +1,127 @@
+use axum::Json;
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Item {
+    pub id: i32,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub next: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[serde(tag = "status", content = "body")]
+pub enum ApiEnvelope<T, E> {
+    Success(T),
+    Failure(E),
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Tree<T> {
+    pub value: T,
+    pub children: Vec<Tree<T>>,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Catalog {
+    pub items: Page<Item>,
+    pub names: Page<String>,
+    pub lookup: ApiEnvelope<Item, String>,
+    pub tree: Tree<i32>,
+}
+
+pub mod other {
+    use super::*;
+
+    /// Shares its name with `generics::Item`
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Item {
+        pub name: String,
+    }
+}
+
+pub async fn post_pages(Json(_page): Json<Page<Item>>) -> Json<Page<other::Item>> {
+    Json(Page { items: Vec::new(), total: 0, next: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use tsclient::{ExportOptions, TypeNaming, WideIntegers, axum::{routing::post, Router}};
+
+    use super::*;
+    use crate::{render_dto, rendered_files};
+
+    #[test]
+    fn generic_types_are_declared_once() {
+        let files = rendered_files::<Catalog>();
+
+        assert!(files.contains(&String::from("Page")), "{:?}", files);
+        assert!(!files.contains(&String::from("Page1")), "{:?}", files);
+
+        let page = render_dto::<Catalog>("Page");
+
+        assert!(page.contains("export type Page<T> = {"), "{}", page);
+        assert!(page.contains("\titems: Array<T>;"), "{}", page);
+        assert!(page.contains("\tnext: number | null"), "{}", page);
+    }
+
+    #[test]
+    fn options_are_written_in_place() {
+        let files = rendered_files::<Catalog>();
+
+        assert!(!files.contains(&String::from("Option")), "{:?}", files);
+        assert!(!render_dto::<Catalog>("Page").contains("import"));
+        assert_eq!(Option::<Item>::ts_name(WideIntegers::Number), "Item | null");
+    }
+
+    #[test]
+    fn instantiations_are_referenced_with_arguments() {
+        let content = render_dto::<Catalog>("Catalog");
+
+        assert!(content.contains("\titems: Page<Item>;"), "{}", content);
+        assert!(content.contains("\tnames: Page<string>;"), "{}", content);
+        assert!(content.contains("\tlookup: ApiEnvelope<Item, string>;"), "{}", content);
+        assert!(content.contains("\ttree: Tree<number>"), "{}", content);
+        assert_eq!(Page::<Item>::ts_name(WideIntegers::Number), "Page<Item>");
+    }
+
+    #[test]
+    fn generic_enums_carry_their_parameters() {
+        let content = render_dto::<Catalog>("ApiEnvelope");
+
+        assert!(content.contains("export type Success<T, E> = {"), "{}", content);
+        assert!(content.contains("\tbody: T"), "{}", content);
+        assert!(content.contains("export type ApiEnvelope<T, E> = Success<T, E> | Failure<T, E>"), "{}", content);
+    }
+
+    #[test]
+    fn recursive_generics_refer_to_themselves() {
+        let content = render_dto::<Catalog>("Tree");
+
+        assert!(content.contains("export type Tree<T> = {"), "{}", content);
+        assert!(content.contains("\tchildren: Array<Tree<T>>"), "{}", content);
+    }
+
+    #[test]
+    fn route_arguments_use_the_exported_names() {
+        let router = Router::<()>::new().route("/pages", post(post_pages));
+
+        for (naming, item, other) in [(TypeNaming::Numbered, "Item", "Item1"), (TypeNaming::ModulePath, "GenericsItem", "OtherItem")] {
+            let files = router.api.render(&ExportOptions::new().type_naming(naming)).unwrap().files;
+
+            let routes = &files[Path::new("api/pages.ts")];
+            assert!(routes.contains(&format!("postPages(pageOfItem: PageInput<{}>): Promise<ApiResult<Page<{}>, any>>", item, other)), "{}", routes);
+            for name in [item, other] {
+                assert!(routes.contains(&format!("import {{ type {} }} from \"../dto/{}\";", name, name)), "{}", routes);
+            }
+            assert!(files[&Path::new("dto").join(format!("{}.ts", other))].contains("\tname: string"));
+        }
+    }
+}
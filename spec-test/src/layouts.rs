@@ -0,0 +1,163 @@
+use axum::{extract::Query, Json};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+pub mod billing {
+    use serde::{Deserialize, Serialize};
+    use tsclient::{TypeScriptStrict, prelude::*};
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Invoice {
+        pub number: u32,
+    }
+}
+
+pub mod shipping {
+    use serde::{Deserialize, Serialize};
+    use tsclient::{TypeScriptStrict, prelude::*};
+
+    use super::billing::Invoice;
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Parcel {
+        pub invoice: Invoice,
+        pub weight: f64,
+    }
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct ParcelFilter {
+        pub heavy: Option<bool>,
+    }
+}
+
+/// `Outline` and `Fill` both have a `Circle` variant, which is also the name of a struct
+pub mod shapes {
+    use serde::{Deserialize, Serialize};
+    use tsclient::{TypeScriptStrict, prelude::*};
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub enum Outline {
+        Circle { radius: f64 },
+        Square { side: f64 },
+    }
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub enum Fill {
+        Circle { color: String },
+        Solid { color: String },
+    }
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Circle {
+        pub radius: f64,
+    }
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Drawing {
+        pub outline: Outline,
+        pub fill: Fill,
+        pub marker: Circle,
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, TypeScriptStrict)]
+pub struct InvoiceFilter {
+    pub paid: Option<bool>,
+}
+
+pub async fn list_invoices(Query(_filter): Query<InvoiceFilter>) -> Json<Vec<billing::Invoice>> {
+    Json(Vec::new())
+}
+
+pub async fn list_parcels(Query(_filter): Query<shipping::ParcelFilter>) -> Json<Vec<shipping::Parcel>> {
+    Json(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use tsclient::{ExportOptions, Layout, api::RenderedFiles, axum::{routing::get, Router}};
+
+    use super::*;
+
+    fn render(layout: Layout) -> RenderedFiles {
+        Router::<()>::new()
+            .route("/billing/invoices", get(list_invoices))
+            .route("/shipping/parcels", get(list_parcels))
+            .route("/archive/parcels", get(list_parcels))
+            .api.render(&ExportOptions::new().layout(layout)).unwrap().files
+    }
+
+    fn file<'a>(files: &'a RenderedFiles, path: &str) -> &'a str {
+        files.get(Path::new(path)).unwrap_or_else(|| panic!("{} is missing from {:?}", path, files.keys()))
+    }
+
+    #[test]
+    fn per_module() {
+        let files = render(Layout::PerModule);
+
+        let billing = file(&files, "dto/layouts/billing.ts");
+        assert!(billing.contains("export type Invoice = {"), "{}", billing);
+
+        let shipping = file(&files, "dto/layouts/shipping.ts");
+        assert!(shipping.contains("export type Parcel = {"), "{}", shipping);
+        assert!(shipping.contains("export type ParcelFilter = {"), "{}", shipping);
+        assert!(shipping.contains("import {type Invoice} from \"../layouts/billing\";"), "{}", shipping);
+
+        let routes = file(&files, "api/shipping/parcels.ts");
+        assert!(routes.contains("import { type Parcel } from \"../../dto/layouts/shipping\";"), "{}", routes);
+        assert!(routes.contains("from \"../client\""), "{}", routes);
+        assert!(!files.contains_key(Path::new("dto/Invoice.ts")));
+    }
+
+    #[test]
+    fn bundled() {
+        let files = render(Layout::Bundled);
+        let paths: Vec<&PathBuf> = files.keys().collect();
+        assert_eq!(paths, [Path::new("client.ts"), Path::new("types.ts")]);
+
+        let types = file(&files, "types.ts");
+        assert!(types.contains("export type Invoice = {") && types.contains("export type Parcel = {"), "{}", types);
+        assert!(!types.contains("import "), "{}", types);
+
+        let client = file(&files, "client.ts");
+        assert!(client.contains("import { type Parcel } from \"./types\";"), "{}", client);
+        assert!(client.contains("const __client__ = client;"), "{}", client);
+        assert!(client.contains("export async function getInvoices("), "{}", client);
+        // Both `parcels` routes are named after their last segment
+        assert!(client.contains("export async function getParcels("), "{}", client);
+        assert!(client.contains("export async function getParcels1("), "{}", client);
+        assert!(client.contains("getParcels: getParcels1"), "{}", client);
+        assert!(!client.contains("import __client__"), "{}", client);
+    }
+
+    #[test]
+    fn single_file() {
+        let files = render(Layout::SingleFile);
+        let paths: Vec<&PathBuf> = files.keys().collect();
+        assert_eq!(paths, [Path::new("client.ts")]);
+
+        let client = file(&files, "client.ts");
+        assert!(client.contains("export type Invoice = {"), "{}", client);
+        assert!(client.contains("export async function getInvoices("), "{}", client);
+        assert!(client.find("export type Parcel").unwrap() < client.find("class Client").unwrap(), "{}", client);
+        assert!(!client.contains("import "), "{}", client);
+        assert_eq!(client.matches("automagically").count(), 1, "{}", client);
+    }
+
+    #[test]
+    fn merged_files_fail_on_clashing_exports() {
+        let router = Router::<()>::new().add_type::<shapes::Drawing>();
+
+        for layout in [Layout::PerModule, Layout::Bundled, Layout::SingleFile] {
+            let error = router.api.render(&ExportOptions::new().layout(layout)).unwrap_err().to_string();
+            assert!(error.contains("exported as `Circle`"), "{}", error);
+            assert!(error.contains("`spec_test::layouts::shapes::"), "{}", error);
+        }
+
+        let files = router.api.render(&ExportOptions::new()).unwrap().files;
+        for path in ["dto/Circle.ts", "dto/Outline.ts", "dto/Fill.ts"] {
+            assert!(file(&files, path).contains("export type Circle = {"), "{}", path);
+        }
+    }
+}
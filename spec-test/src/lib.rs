@@ -3,6 +3,81 @@ pub fn add(left: usize, right: usize) -> usize {
 }
 
 pub mod testes;
+pub mod renames;
+pub mod optional;
+pub mod flatten;
+pub mod proxies;
+pub mod representations;
+pub mod directions;
+pub mod generics;
+pub mod ts_attrs;
+pub mod docs;
+pub mod generic_params;
+pub mod unit_enums;
+pub mod std_types;
+pub mod ecosystem;
+pub mod wide_integers;
+pub mod recursive;
+pub mod determinism;
+pub mod naming;
+pub mod registries;
+pub mod exported;
+pub mod brands;
+pub mod export_options;
+pub mod render;
+pub mod check;
+pub mod safe_export;
+pub mod layouts;
+
+#[cfg(test)]
+pub(crate) fn render_dto<T: tsclient::types::TypescriptType>(file: &str) -> String {
+    use tsclient::types::builder::{GlobalTypeRegistry, TypeBuilder};
+
+    let mut registry = GlobalTypeRegistry::new();
+    T::get_definition(&mut registry);
+
+    let builder = TypeBuilder::build(&registry);
+    let content = builder.file_map.get(file).expect("Type was not exported").lock().unwrap().content.clone();
+    content
+}
+
+#[cfg(test)]
+pub(crate) fn dto_imports<T: tsclient::types::TypescriptType>(file: &str) -> Vec<String> {
+    use tsclient::types::builder::{GlobalTypeRegistry, TypeBuilder};
+
+    let mut registry = GlobalTypeRegistry::new();
+    T::get_definition(&mut registry);
+
+    let builder = TypeBuilder::build(&registry);
+    let imports = builder.file_map.get(file).expect("Type was not exported").lock().unwrap().imports.clone();
+    imports.into_iter().map(|(name, _)| name).collect()
+}
+
+#[cfg(test)]
+pub(crate) fn rendered_files<T: tsclient::types::TypescriptType>() -> Vec<String> {
+    use tsclient::types::builder::{GlobalTypeRegistry, TypeBuilder};
+
+    let mut registry = GlobalTypeRegistry::new();
+    T::get_definition(&mut registry);
+
+    TypeBuilder::build(&registry).file_map.keys().cloned().collect()
+}
+
+/// An empty folder in the system temp dir that no other test, or earlier run, writes to
+#[cfg(test)]
+pub(crate) fn temp_dir(name: &str) -> std::path::PathBuf {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+    let path = std::env::temp_dir().join(format!("tsclient-{}-{}-{}", name, std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed)));
+    if path.exists() {
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+    std::fs::create_dir_all(&path).unwrap();
+
+    path
+}
 
 #[cfg(test)]
 mod tests {
@@ -11,6 +86,7 @@ mod tests {
     use serde::{Serialize, Deserialize};
     use tsclient::TypeScript;
     use tsclient::TypeScriptStrict;
+    use tsclient::ExportOptions;
     use tsclient::api::HTTPMethod;
     use tsclient::axum::Router;
     use tsclient::axum::routing::post;
@@ -116,7 +192,10 @@ mod tests {
             .route("/api/test", post(test_fn).get(test_fn3))
             .route("/api/test/deep/and/nested", post(test_fn2).rename_ts([(HTTPMethod::POST, "createNested")]));
 
-        api.api.export_to(&std::path::PathBuf::from("test/api"), Some("/api")).unwrap();
+        let files = api.api.render(&ExportOptions::new().remove_prefix("/api")).unwrap().files;
+        for (path, content) in files.iter() {
+            println!("{}\n{}", path.display(), content);
+        }
 
         println!();
         let t7: Test3 = Test3 {
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+pub mod admin {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct User {
+        pub name: String,
+        pub permissions: Vec<String>,
+    }
+}
+
+pub mod public {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct User {
+        pub name: String,
+    }
+}
+
+pub mod billing {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    #[ts(rename = "Account")]
+    pub struct Customer {
+        pub name: String,
+    }
+
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Account {
+        pub iban: String,
+    }
+
+    /// Both accounts live in the same module, their module path can't tell them apart
+    #[derive(Serialize, Deserialize, TypeScriptStrict)]
+    pub struct Ledger {
+        pub customer: Customer,
+        pub account: Account,
+    }
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Users {
+    pub admin: admin::User,
+    pub public: public::User,
+    pub timeout: std::time::Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::{ExportOptions, TypeNaming, types::builder::{GlobalTypeRegistry, TypeBuilder}};
+
+    use super::*;
+
+    fn build(naming: TypeNaming) -> TypeBuilder {
+        let mut registry = GlobalTypeRegistry::new();
+        Users::get_definition(&mut registry);
+
+        TypeBuilder::build_with_options(&registry, &ExportOptions::new().type_naming(naming))
+    }
+
+    fn content(builder: &TypeBuilder, file: &str) -> String {
+        builder.file_map.get(file).expect("Type was not exported").lock().unwrap().content.clone()
+    }
+
+    #[test]
+    fn numbered_by_default() {
+        let builder = build(TypeNaming::default());
+        let content = content(&builder, "Users");
+
+        assert!(content.contains("\tadmin: User;"), "{}", content);
+        assert!(content.contains("\tpublic: User1;"), "{}", content);
+        assert!(content.contains("\ttimeout: Duration"), "{}", content);
+    }
+
+    #[test]
+    fn module_path() {
+        let builder = build(TypeNaming::ModulePath);
+        let content = content(&builder, "Users");
+
+        assert!(content.contains("\tadmin: AdminUser;"), "{}", content);
+        assert!(content.contains("\tpublic: PublicUser;"), "{}", content);
+        assert!(builder.file_map.contains_key("AdminUser"));
+        assert!(!builder.file_map.contains_key("User"));
+        assert_eq!(builder.file_path("AdminUser"), "AdminUser");
+    }
+
+    #[test]
+    fn folders() {
+        let builder = build(TypeNaming::Folders);
+
+        assert_eq!(builder.file_path("AdminUser"), "naming/admin/AdminUser");
+        assert_eq!(builder.file_path("Users"), "naming/Users");
+        assert_eq!(builder.file_path("Duration"), "Duration");
+
+        let dto = crate::temp_dir("naming-folders");
+        builder.export_to(&dto).unwrap();
+
+        let users = std::fs::read_to_string(dto.join("naming/Users.ts")).unwrap();
+        assert!(users.contains("import {type AdminUser} from \"../naming/admin/AdminUser\";"), "{}", users);
+        assert!(users.contains("import {type Duration} from \"../Duration\";"), "{}", users);
+        assert!(dto.join("naming/public/PublicUser.ts").exists());
+    }
+
+    #[test]
+    fn collisions_fail_the_export() {
+        let builder = build(TypeNaming::Error);
+
+        let error = builder.export_to(&crate::temp_dir("naming-error")).unwrap_err().to_string();
+        assert!(error.contains("`spec_test::naming::admin::User`"), "{}", error);
+        assert!(error.contains("`spec_test::naming::public::User`"), "{}", error);
+    }
+
+    #[test]
+    fn module_path_fails_when_modules_are_the_same() {
+        let mut registry = GlobalTypeRegistry::new();
+        billing::Ledger::get_definition(&mut registry);
+
+        let options = ExportOptions::new().type_naming(TypeNaming::ModulePath);
+        let error = TypeBuilder::build_with_options(&registry, &options).render().unwrap_err().to_string();
+        assert!(error.contains("`spec_test::naming::billing::Customer`"), "{}", error);
+        assert!(error.contains("`spec_test::naming::billing::Account`"), "{}", error);
+    }
+}
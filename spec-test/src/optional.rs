@@ -0,0 +1,78 @@
+use std::sync::Mutex;
+
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Profile {
+    pub id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(skip)]
+    pub cache: Mutex<Vec<u8>>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: String,
+    pub page_size: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { theme: String::from("dark"), page_size: 25 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn optional_and_skipped_fields() {
+        let content = render_dto::<Profile>("Profile");
+
+        assert!(content.contains("\tid: number;"), "{}", content);
+        assert!(content.contains("\tnickname?: "), "{}", content);
+        assert!(content.contains("\ttags: Array<string>"), "{}", content);
+        assert!(!content.contains("cache"), "{}", content);
+        assert!(!content.contains("secret"), "{}", content);
+
+        // `default` only matters when reading, missing options are read as `None`
+        let input = render_dto::<Profile>("ProfileInput");
+
+        assert!(input.contains("\tid: number;"), "{}", input);
+        assert!(input.contains("\tnickname?: "), "{}", input);
+        assert!(input.contains("\ttags?: Array<string>"), "{}", input);
+        assert!(!input.contains("cache"), "{}", input);
+        assert!(!input.contains("secret"), "{}", input);
+
+        let json = serde_json::to_string(&Profile {
+            id: 1,
+            nickname: None,
+            tags: vec![],
+            cache: Mutex::new(vec![]),
+            secret: String::new(),
+        }).unwrap();
+        assert_eq!(json, r#"{"id":1,"tags":[]}"#);
+    }
+
+    #[test]
+    fn container_default_makes_all_fields_optional() {
+        let content = render_dto::<Settings>("SettingsInput");
+
+        assert!(content.contains("\ttheme?: string;"), "{}", content);
+        assert!(content.contains("\tpage_size?: number"), "{}", content);
+
+        let output = render_dto::<Settings>("Settings");
+
+        assert!(output.contains("\ttheme: string;"), "{}", output);
+        assert!(output.contains("\tpage_size: number"), "{}", output);
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, Clone, Copy, TypeScriptStrict)]
+#[serde(transparent)]
+pub struct UserId(i64);
+
+#[derive(Serialize, Deserialize, Clone, TypeScriptStrict)]
+#[serde(transparent)]
+pub struct Email {
+    value: String,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    verified: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, TypeScriptStrict)]
+pub struct RgbWire(u8, u8, u8);
+
+#[derive(Serialize, Deserialize, Clone, TypeScriptStrict)]
+#[serde(into = "RgbWire", from = "RgbWire")]
+pub struct Color {
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+impl From<Color> for RgbWire {
+    fn from(c: Color) -> Self {
+        RgbWire(c.red, c.green, c.blue)
+    }
+}
+
+impl From<RgbWire> for Color {
+    fn from(w: RgbWire) -> Self {
+        Color { red: w.0, green: w.1, blue: w.2 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, TypeScriptStrict)]
+#[serde(try_from = "String")]
+pub struct Slug(String);
+
+impl TryFrom<String> for Slug {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+            Ok(Slug(value))
+        } else {
+            Err("invalid slug")
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Account {
+    pub id: UserId,
+    pub email: Email,
+    pub color: Color,
+    pub slug: Slug,
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::WideIntegers;
+
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn proxies_use_the_wire_type() {
+        let content = render_dto::<Account>("Account");
+
+        assert!(content.contains("\tid: number;"), "{}", content);
+        assert!(content.contains("\temail: string;"), "{}", content);
+        assert!(content.contains("\tcolor: RgbWire;"), "{}", content);
+        assert!(content.contains("\tslug: string"), "{}", content);
+        assert_eq!(Color::ts_name(WideIntegers::Number), "RgbWire");
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct TreeNode {
+    pub name: String,
+    pub children: Vec<TreeNode>,
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Department {
+    pub name: String,
+    pub head: Option<Box<Employee>>,
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Employee {
+    pub name: String,
+    pub department: Department,
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+#[serde(tag = "type")]
+pub enum Expr {
+    Literal { value: i32 },
+    Add { left: Box<Expr>, right: Box<Expr> },
+    Call { args: Vec<Expr>, named: HashMap<String, Expr> },
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<Box<Page<T>>>,
+}
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Folder {
+    pub files: Page<Folder>,
+    pub parent: Option<Box<Folder>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::types::builder::GlobalTypeRegistry;
+
+    use super::*;
+    use crate::{dto_imports, render_dto};
+
+    #[test]
+    fn cycle_hashes_do_not_depend_on_the_entry_point() {
+        let mut employee_first = GlobalTypeRegistry::new();
+        let employee = Employee::hash(&mut employee_first);
+        let department = Department::hash(&mut employee_first);
+
+        let mut department_first = GlobalTypeRegistry::new();
+        assert_eq!(Department::hash(&mut department_first), department);
+        assert_eq!(Employee::hash(&mut department_first), employee);
+
+        assert_ne!(employee, department);
+    }
+
+    #[test]
+    fn self_reference() {
+        let content = render_dto::<TreeNode>("TreeNode");
+
+        assert!(content.contains("export type TreeNode = {"), "{}", content);
+        assert!(!dto_imports::<TreeNode>("TreeNode").contains(&String::from("TreeNode")));
+    }
+
+    #[test]
+    fn mutual_recursion_imports_both_ways() {
+        assert!(render_dto::<Employee>("Employee").contains("\tdepartment: Department"));
+        assert!(render_dto::<Employee>("Department").contains("\thead: Employee | null"));
+
+        assert!(dto_imports::<Employee>("Employee").contains(&String::from("Department")));
+        assert!(dto_imports::<Employee>("Department").contains(&String::from("Employee")));
+    }
+
+    #[test]
+    fn recursive_enum() {
+        let content = render_dto::<Expr>("Expr");
+
+        assert!(content.contains("\tleft: Expr;"), "{}", content);
+        assert!(content.contains("export type Expr = Literal | Add | Call"), "{}", content);
+        assert!(!dto_imports::<Expr>("Expr").contains(&String::from("Expr")));
+    }
+
+    #[test]
+    fn recursion_through_generics() {
+        let content = render_dto::<Folder>("Folder");
+
+        assert!(content.contains("\tfiles: Page<Folder>;"), "{}", content);
+        assert!(content.contains("\tparent: Folder | null"), "{}", content);
+        assert!(render_dto::<Folder>("Page").contains("\tnext: Page<T> | null"));
+    }
+}
@@ -0,0 +1,73 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Invoice {
+    pub number: u32,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Shipment {
+    pub carrier: String,
+}
+
+pub async fn get_invoice() -> Json<Invoice> {
+    Json(Invoice { number: 0 })
+}
+
+pub async fn get_shipment() -> Json<Shipment> {
+    Json(Shipment { carrier: String::new() })
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::{axum::{routing::get, Router}, types::builder::TypeBuilder};
+
+    use super::*;
+
+    fn exported(router: &Router<()>) -> Vec<String> {
+        let registry = router.api.components.lock().unwrap();
+
+        TypeBuilder::build(&registry).file_map.keys().cloned().collect()
+    }
+
+    fn billing() -> Router<()> {
+        Router::<()>::new().route("/invoice", get(get_invoice))
+    }
+
+    fn shipping() -> Router<()> {
+        Router::<()>::new().route("/shipment", get(get_shipment))
+    }
+
+    #[test]
+    fn routers_only_export_their_own_types() {
+        let billing = exported(&billing());
+        let shipping = exported(&shipping());
+
+        assert!(billing.contains(&String::from("Invoice")), "{:?}", billing);
+        assert!(!billing.contains(&String::from("Shipment")), "{:?}", billing);
+        assert!(shipping.contains(&String::from("Shipment")), "{:?}", shipping);
+        assert!(!shipping.contains(&String::from("Invoice")), "{:?}", shipping);
+    }
+
+    #[test]
+    fn nest_and_merge_combine_registries() {
+        let nested = exported(&Router::<()>::new().nest("/billing", billing()).nest("/shipping", shipping()));
+        let merged = exported(&billing().merge(shipping()));
+
+        for types in [nested, merged] {
+            assert!(types.contains(&String::from("Invoice")), "{:?}", types);
+            assert!(types.contains(&String::from("Shipment")), "{:?}", types);
+        }
+    }
+
+    #[test]
+    fn global_registry_is_shared() {
+        let first = Router::<()>::with_global_registry().route("/invoice", get(get_invoice));
+        let second = Router::<()>::with_global_registry().route("/shipment", get(get_shipment));
+
+        assert!(std::sync::Arc::ptr_eq(&first.api.components, &second.api.components));
+        assert!(exported(&first).contains(&String::from("Shipment")));
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScript, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScript)]
+#[serde(rename_all = "camelCase")]
+pub struct RenamedUser {
+    pub user_id: i32,
+    #[serde(rename = "display-name")]
+    pub display_name: String,
+    #[serde(rename(serialize = "mail", deserialize = "email"))]
+    pub e_mail: String,
+    pub r#type: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+#[serde(rename = "Event")]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum RenamedEvent {
+    UserCreated { user_id: i32 },
+    #[serde(rename = "deleted")]
+    UserDeleted(i32),
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    UserMoved { from_id: i32, to_id: i32 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn struct_fields_follow_serde_renames() {
+        let content = render_dto::<RenamedUser>("RenamedUser");
+
+        assert!(content.contains("\tuserId: number;"), "{}", content);
+        assert!(content.contains("\t\"display-name\": string;"), "{}", content);
+        assert!(content.contains("\tmail: string;"), "{}", content);
+        assert!(content.contains("\ttype: string"), "{}", content);
+    }
+
+    #[test]
+    fn enum_variants_follow_serde_renames() {
+        let content = render_dto::<RenamedEvent>("Event");
+
+        assert!(content.contains("kind: \"user_created\""), "{}", content);
+        assert!(content.contains("\tuserId: number"), "{}", content);
+        assert!(content.contains("kind: \"deleted\""), "{}", content);
+        assert!(content.contains("\tFROM_ID: number;"), "{}", content);
+        assert!(content.contains("export type Event = UserCreated | Deleted | UserMoved"), "{}", content);
+
+        let json = serde_json::to_string(&RenamedEvent::UserCreated { user_id: 1 }).unwrap();
+        assert_eq!(json, r#"{"kind":"user_created","data":{"userId":1}}"#);
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{extract::Query, Json};
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Book {
+    pub title: String,
+    pub author: Author,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Author {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct BookFilter {
+    pub author: Option<String>,
+}
+
+pub async fn list_books(Query(_filter): Query<BookFilter>) -> Json<Vec<Book>> {
+    Json(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::{Path, PathBuf}};
+
+    use tsclient::{ExportOptions, axum::{routing::get, Router}};
+
+    use super::*;
+
+    fn router() -> Router<()> {
+        Router::<()>::new()
+            .route("/api/library/books", get(list_books))
+    }
+
+    #[test]
+    fn renders_without_touching_the_disk() {
+        let files = router().api.render(&ExportOptions::new().remove_prefix("/api")).unwrap().files;
+        let paths: Vec<&Path> = files.keys().map(PathBuf::as_path).collect();
+
+        for path in ["api/client.ts", "api/library/books.ts", "dto/Author.ts", "dto/Book.ts", "dto/BookFilter.ts"] {
+            assert!(paths.contains(&Path::new(path)), "{:?}", paths);
+        }
+        assert!(paths.iter().all(|path| path.starts_with("api") || path.starts_with("dto")), "{:?}", paths);
+        assert!(files[Path::new("dto/Book.ts")].contains("import {type Author} from \"./Author\";"));
+        assert!(files[Path::new("api/library/books.ts")].contains("export async function getBooks("));
+    }
+
+    #[test]
+    fn export_writes_the_rendered_files() {
+        let options = ExportOptions::new().remove_prefix("/api");
+        let path = crate::temp_dir("render");
+
+        router().api.export(&path, &options).unwrap();
+
+        for (file, content) in router().api.render(&options).unwrap().files {
+            assert_eq!(fs::read_to_string(path.join(&file)).unwrap(), content, "{}", file.display());
+        }
+    }
+}
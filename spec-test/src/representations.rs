@@ -0,0 +1,143 @@
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScript, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub enum External {
+    Unit,
+    Newtype(i32),
+    Tuple(i32, String),
+    Struct { id: i32 },
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+#[serde(tag = "type")]
+pub enum Internal {
+    Unit,
+    Newtype(Point),
+    Struct { id: i32 },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+#[serde(tag = "t", content = "c")]
+pub enum Adjacent {
+    Unit,
+    Newtype(i32),
+    Tuple(i32, String),
+    Struct { id: i32 },
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+#[serde(untagged)]
+pub enum Untagged {
+    Unit,
+    Newtype(i32),
+    Tuple(i32, String),
+    Struct { id: i32 },
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub enum PartiallyUntagged {
+    Known(i32),
+    #[serde(untagged)]
+    Fallback(String),
+}
+
+#[derive(Serialize, Deserialize, TypeScript)]
+pub enum Shadowing {
+    Point(Point),
+    Shadowing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    fn json<T: Serialize>(value: &T) -> String {
+        serde_json::to_string(value).unwrap()
+    }
+
+    #[test]
+    fn externally_tagged() {
+        let content = render_dto::<External>("External");
+
+        assert_eq!(json(&External::Unit), r#""Unit""#);
+        assert!(content.contains("export type Unit = \"Unit\""), "{}", content);
+        assert_eq!(json(&External::Newtype(1)), r#"{"Newtype":1}"#);
+        assert!(content.contains("export type Newtype = {\n\tNewtype: number\n}"), "{}", content);
+        assert_eq!(json(&External::Tuple(1, String::new())), r#"{"Tuple":[1,""]}"#);
+        assert!(content.contains("export type Tuple = {\n\tTuple: [ number, string]\n}"), "{}", content);
+        assert_eq!(json(&External::Struct { id: 1 }), r#"{"Struct":{"id":1}}"#);
+        assert!(content.contains("export type Struct = {\n\tStruct: {\n\tid: number\n}\n}"), "{}", content);
+        assert!(content.contains("export type External = Unit | Newtype | Tuple | Struct"), "{}", content);
+    }
+
+    #[test]
+    fn internally_tagged() {
+        let content = render_dto::<Internal>("Internal");
+
+        assert_eq!(json(&Internal::Unit), r#"{"type":"Unit"}"#);
+        assert!(content.contains("export type Unit = {\n\ttype: \"Unit\";\n}"), "{}", content);
+        assert_eq!(json(&Internal::Newtype(Point { x: 1, y: 2 })), r#"{"type":"Newtype","x":1,"y":2}"#);
+        assert!(content.contains("export type Newtype = {\n\ttype: \"Newtype\"\n} & Point"), "{}", content);
+        assert_eq!(json(&Internal::Struct { id: 1 }), r#"{"type":"Struct","id":1}"#);
+        assert!(content.contains("export type Struct = {\n\ttype: \"Struct\";\n\tid: number\n}"), "{}", content);
+        assert_eq!(json(&Internal::Unknown), r#"{"type":"Unknown"}"#);
+        assert!(content.contains("export type Unknown = {\n\ttype: \"Unknown\";\n}"), "{}", content);
+    }
+
+    #[test]
+    fn adjacently_tagged() {
+        let content = render_dto::<Adjacent>("Adjacent");
+
+        assert_eq!(json(&Adjacent::Unit), r#"{"t":"Unit"}"#);
+        assert!(content.contains("export type Unit = {\n\tt: \"Unit\";\n}"), "{}", content);
+        assert_eq!(json(&Adjacent::Newtype(1)), r#"{"t":"Newtype","c":1}"#);
+        assert!(content.contains("export type Newtype = {\n\tt: \"Newtype\";\n\tc: number\n}"), "{}", content);
+        assert_eq!(json(&Adjacent::Tuple(1, String::new())), r#"{"t":"Tuple","c":[1,""]}"#);
+        assert!(content.contains("export type Tuple = {\n\tt: \"Tuple\";\n\tc: [ number, string]\n}"), "{}", content);
+        assert_eq!(json(&Adjacent::Struct { id: 1 }), r#"{"t":"Struct","c":{"id":1}}"#);
+        assert!(content.contains("export type Struct = {\n\tt: \"Struct\";\n\tc: {\n\tid: number\n}\n}"), "{}", content);
+    }
+
+    #[test]
+    fn untagged() {
+        let content = render_dto::<Untagged>("Untagged");
+
+        assert_eq!(json(&Untagged::Unit), "null");
+        assert!(content.contains("export type Unit = null"), "{}", content);
+        assert_eq!(json(&Untagged::Newtype(1)), "1");
+        assert!(content.contains("export type Newtype = number"), "{}", content);
+        assert_eq!(json(&Untagged::Tuple(1, String::new())), r#"[1,""]"#);
+        assert!(content.contains("export type Tuple = [ number, string]"), "{}", content);
+        assert_eq!(json(&Untagged::Struct { id: 1 }), r#"{"id":1}"#);
+        assert!(content.contains("export type Struct = {\n\tid: number\n}"), "{}", content);
+    }
+
+    #[test]
+    fn untagged_variant() {
+        let content = render_dto::<PartiallyUntagged>("PartiallyUntagged");
+
+        assert_eq!(json(&PartiallyUntagged::Known(1)), r#"{"Known":1}"#);
+        assert_eq!(json(&PartiallyUntagged::Fallback(String::from("x"))), r#""x""#);
+        assert!(content.contains("export type Known = {\n\tKnown: number\n}"), "{}", content);
+        assert!(content.contains("export type Fallback = string"), "{}", content);
+    }
+
+    #[test]
+    fn variant_aliases_do_not_shadow_other_types() {
+        let content = render_dto::<Shadowing>("Shadowing");
+
+        assert!(content.contains("export type ShadowingPoint = {\n\tPoint: Point\n}"), "{}", content);
+        assert!(content.contains("export type ShadowingShadowing = \"Shadowing\""), "{}", content);
+        assert!(content.contains("export type Shadowing = ShadowingPoint | ShadowingShadowing"), "{}", content);
+    }
+}
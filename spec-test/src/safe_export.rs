@@ -0,0 +1,113 @@
+use axum::{extract::Query, Json};
+use serde::{Deserialize, Serialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Ticket {
+    pub title: String,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct TicketFilter {
+    pub open: Option<bool>,
+}
+
+pub async fn list_tickets(Query(_filter): Query<TicketFilter>) -> Json<Vec<Ticket>> {
+    Json(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::{Component, PathBuf}, time::SystemTime};
+
+    use tsclient::{ExportOptions, axum::{routing::get, Router}, types::builder::{GlobalTypeRegistry, TypeBuilder}};
+
+    use super::*;
+    use crate::temp_dir;
+
+    #[test]
+    fn unchanged_files_are_not_written() {
+        let path = temp_dir("safe-unchanged");
+        let router = || Router::<()>::new().route("/tickets", get(list_tickets));
+
+        router().api.export(&path, &ExportOptions::new()).unwrap();
+        let ticket = fs::File::options().write(true).open(path.join("dto/Ticket.ts")).unwrap();
+        ticket.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+
+        router().api.export(&path, &ExportOptions::new()).unwrap();
+        assert_eq!(fs::metadata(path.join("dto/Ticket.ts")).unwrap().modified().unwrap(), SystemTime::UNIX_EPOCH);
+    }
+
+    #[test]
+    fn only_stale_generated_files_are_removed() {
+        let path = temp_dir("safe-stale");
+
+        Router::<()>::new()
+            .route("/tickets", get(list_tickets))
+            .route("/archive/tickets", get(list_tickets))
+            .api.export(&path, &ExportOptions::new()).unwrap();
+        assert!(path.join("api/archive/tickets.ts").exists());
+        fs::write(path.join("api/handwritten.ts"), "export const x = 1;").unwrap();
+
+        Router::<()>::new()
+            .route("/tickets", get(list_tickets))
+            .api.export(&path, &ExportOptions::new()).unwrap();
+
+        assert!(!path.join("api/archive").exists());
+        assert!(path.join("api/tickets.ts").exists());
+        assert!(path.join("api/handwritten.ts").exists());
+    }
+
+    #[test]
+    fn files_that_were_not_generated_are_not_overwritten() {
+        let path = temp_dir("safe-foreign");
+        fs::create_dir_all(path.join("dto")).unwrap();
+        fs::write(path.join("dto/Ticket.ts"), "// my own code").unwrap();
+
+        let router = Router::<()>::new().route("/tickets", get(list_tickets));
+        let error = router.api.export(&path, &ExportOptions::new()).unwrap_err();
+
+        assert!(error.to_string().contains("Ticket.ts"), "{}", error);
+        assert_eq!(fs::read_to_string(path.join("dto/Ticket.ts")).unwrap(), "// my own code");
+        assert!(!path.join("api").exists());
+    }
+
+    #[test]
+    fn type_builder_exports_are_checked_too() {
+        let path = temp_dir("safe-type-builder");
+        fs::write(path.join("Ticket.ts"), "// my own code").unwrap();
+
+        let mut registry = GlobalTypeRegistry::new();
+        Ticket::get_definition(&mut registry);
+        let error = TypeBuilder::build(&registry).export_to(&path).unwrap_err();
+
+        assert!(error.to_string().contains("Ticket.ts"), "{}", error);
+        assert_eq!(fs::read_to_string(path.join("Ticket.ts")).unwrap(), "// my own code");
+    }
+
+    #[test]
+    fn files_with_the_header_are_overwritten() {
+        let path = temp_dir("safe-header");
+        fs::create_dir_all(path.join("dto")).unwrap();
+        fs::write(path.join("dto/Ticket.ts"), "// generated\nexport type Ticket = {}").unwrap();
+
+        let options = ExportOptions::new().header("// generated\n");
+        Router::<()>::new().route("/tickets", get(list_tickets)).api.export(&path, &options).unwrap();
+
+        assert!(fs::read_to_string(path.join("dto/Ticket.ts")).unwrap().contains("title: string"));
+    }
+
+    #[test]
+    fn route_segments_are_sanitized() {
+        let router = Router::<()>::new()
+            .route("/../../escape", get(list_tickets))
+            .route("/v1.0/tickets", get(list_tickets));
+        let files = router.api.render(&ExportOptions::new()).unwrap().files;
+
+        for path in files.keys() {
+            assert!(path.components().all(|c| matches!(c, Component::Normal(_))), "{}", path.display());
+        }
+        assert!(files.contains_key(&PathBuf::from("api/__/__/escape.ts")), "{:?}", files.keys());
+        assert!(files.contains_key(&PathBuf::from("api/v1_0/tickets.ts")), "{:?}", files.keys());
+    }
+}
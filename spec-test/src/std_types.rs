@@ -0,0 +1,91 @@
+use std::{borrow::Cow, collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque}, convert::Infallible, net::{IpAddr, SocketAddr}, num::NonZeroU32, path::PathBuf, rc::Rc, sync::Arc, time::{Duration, SystemTime}};
+
+use serde::Serialize;
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, TypeScriptStrict)]
+pub struct Tag {
+    pub name: String,
+}
+
+#[derive(Serialize, PartialEq, Eq, Hash, PartialOrd, Ord, TypeScriptStrict)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+}
+
+#[derive(TypeScriptStrict)]
+pub struct Everything {
+    pub index: usize,
+    pub offset: isize,
+    pub big: u128,
+    pub initial: char,
+    pub boxed: Box<Tag>,
+    pub shared: Rc<str>,
+    pub synced: Arc<[Tag]>,
+    pub borrowed: Cow<'static, str>,
+    pub by_name: HashMap<String, Tag>,
+    pub by_day: BTreeMap<Weekday, u32>,
+    pub unique: HashSet<String>,
+    pub sorted: BTreeSet<i32>,
+    pub queue: VecDeque<Tag>,
+    pub timeout: Duration,
+    pub created: SystemTime,
+    pub ip: IpAddr,
+    pub socket: SocketAddr,
+    pub path: PathBuf,
+    pub count: NonZeroU32,
+    pub impossible: Option<Infallible>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dto_imports, render_dto};
+
+    #[test]
+    fn primitives_and_wrappers() {
+        let content = render_dto::<Everything>("Everything");
+
+        assert!(content.contains("\tindex: number;"), "{}", content);
+        assert!(content.contains("\toffset: number;"), "{}", content);
+        assert!(content.contains("\tbig: number;"), "{}", content);
+        assert!(content.contains("\tinitial: string;"), "{}", content);
+        assert!(content.contains("\tboxed: Tag;"), "{}", content);
+        assert!(content.contains("\tshared: string;"), "{}", content);
+        assert!(content.contains("\tsynced: TagArray;"), "{}", content);
+        assert!(content.contains("\tborrowed: string;"), "{}", content);
+        assert!(dto_imports::<Everything>("Everything").contains(&String::from("Tag")));
+    }
+
+    #[test]
+    fn maps_and_sets() {
+        let content = render_dto::<Everything>("Everything");
+
+        assert!(content.contains("\tby_name: Record<string, Tag>;"), "{}", content);
+        assert!(content.contains("\tby_day: Partial<Record<Weekday, number>>;"), "{}", content);
+        assert!(dto_imports::<Everything>("Everything").contains(&String::from("Weekday")));
+        assert!(content.contains("\tunique: Array<string>;"), "{}", content);
+        assert!(content.contains("\tsorted: Array<number>;"), "{}", content);
+        assert!(content.contains("\tqueue: TagArray;"), "{}", content);
+    }
+
+    #[test]
+    fn time_net_and_misc() {
+        let content = render_dto::<Everything>("Everything");
+
+        assert!(content.contains("\ttimeout: Duration;"), "{}", content);
+        assert!(content.contains("\tcreated: SystemTime;"), "{}", content);
+        assert!(content.contains("\tip: string;"), "{}", content);
+        assert!(content.contains("\tsocket: string;"), "{}", content);
+        assert!(content.contains("\tpath: string;"), "{}", content);
+        assert!(content.contains("\tcount: number;"), "{}", content);
+        assert!(content.contains("\timpossible: never | null"), "{}", content);
+
+        let duration = render_dto::<Everything>("Duration");
+        assert!(duration.contains("export type Duration = {\n\tsecs: number;\n\tnanos: number\n}"), "{}", duration);
+
+        let json = serde_json::to_string(&Duration::new(1, 2)).unwrap();
+        assert_eq!(json, r#"{"secs":1,"nanos":2}"#);
+    }
+}
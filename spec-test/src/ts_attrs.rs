@@ -0,0 +1,111 @@
+use serde::{Serialize, Deserialize};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+/// Has no `TypescriptType` implementation on purpose
+#[derive(Serialize, Deserialize)]
+pub struct Opaque(pub String);
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Dimensions {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Envelope<T, M> {
+    pub data: T,
+    pub meta: Option<M>,
+    pub history: Vec<Envelope<T, M>>,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[ts(type = "`${number}-${number}`")]
+pub struct Version(pub u32, pub u32);
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[ts(rename = "Asset")]
+pub struct StoredAsset {
+    #[ts(type = "string")]
+    pub blob: Opaque,
+    #[ts(rename = "displayName")]
+    pub display_name: String,
+    #[ts(optional)]
+    pub tags: Vec<String>,
+    #[ts(nullable)]
+    pub size: u64,
+    #[ts(inline)]
+    pub dimensions: Dimensions,
+    #[ts(skip)]
+    pub checksum: Opaque,
+    pub version: Version,
+    #[ts(inline)]
+    pub upload: Envelope<i32, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::WideIntegers;
+
+    use super::*;
+    use crate::{dto_imports, render_dto, rendered_files};
+
+    #[test]
+    fn field_types_can_be_replaced() {
+        let content = render_dto::<StoredAsset>("Asset");
+
+        assert!(content.contains("\tblob: string;"), "{}", content);
+        assert!(!content.contains("checksum"), "{}", content);
+    }
+
+    #[test]
+    fn renames_only_touch_typescript() {
+        let content = render_dto::<StoredAsset>("Asset");
+
+        assert!(content.contains("\tdisplayName: string;"), "{}", content);
+        assert_eq!(StoredAsset::ts_name(WideIntegers::Number), "Asset");
+
+        let json = serde_json::to_string(&StoredAsset {
+            blob: Opaque(String::new()),
+            display_name: String::from("logo"),
+            tags: Vec::new(),
+            size: 0,
+            dimensions: Dimensions { width: 1, height: 1 },
+            checksum: Opaque(String::new()),
+            version: Version(1, 0),
+            upload: Envelope { data: 1, meta: None, history: Vec::new() },
+        }).unwrap();
+        assert!(json.contains(r#""display_name":"logo""#), "{}", json);
+    }
+
+    #[test]
+    fn optional_and_nullable_fields() {
+        let content = render_dto::<StoredAsset>("Asset");
+
+        assert!(content.contains("\ttags?: "), "{}", content);
+        assert!(content.contains("\tsize: number | null;"), "{}", content);
+    }
+
+    #[test]
+    fn inlined_types_are_not_imported() {
+        let content = render_dto::<StoredAsset>("Asset");
+
+        assert!(content.contains("\tdimensions: {\n\t\twidth: number;"), "{}", content);
+        assert!(!dto_imports::<StoredAsset>("Asset").contains(&String::from("Dimensions")));
+    }
+
+    #[test]
+    fn inlined_generics_are_instantiated() {
+        let content = render_dto::<StoredAsset>("Asset");
+
+        assert!(content.contains("\tupload: {\n\t\tdata: number;\n\t\tmeta: string | null;\n\t\thistory: Array<Envelope<number, string>>\n\t}"), "{}", content);
+        assert!(dto_imports::<StoredAsset>("Asset").contains(&String::from("Envelope")));
+    }
+
+    #[test]
+    fn containers_can_be_replaced() {
+        let content = render_dto::<StoredAsset>("Asset");
+
+        assert!(content.contains("\tversion: `${number}-${number}`"), "{}", content);
+        assert!(!rendered_files::<StoredAsset>().contains(&String::from("Version")));
+    }
+}
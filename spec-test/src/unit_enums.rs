@@ -0,0 +1,117 @@
+use serde::{Serialize, Deserialize};
+use serde_repr::{Serialize_repr, Deserialize_repr};
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub enum Role {
+    #[ts(label = "Administrator")]
+    Admin,
+    #[serde(rename = "member")]
+    Member,
+    #[serde(skip)]
+    Internal,
+}
+
+/// Priority of a ticket
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[ts(enum_style = "enum")]
+pub enum Priority {
+    Low,
+    /// Needs attention this week
+    High,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[serde(rename_all = "kebab-case")]
+#[ts(enum_style = "const")]
+pub enum Status {
+    InProgress,
+    Done,
+}
+
+/// Written as its discriminant by `serde_repr`
+#[derive(Serialize_repr, Deserialize_repr, TypeScriptStrict)]
+#[ts(repr)]
+#[repr(u8)]
+pub enum Level {
+    Debug = 1,
+    #[ts(label = "Information")]
+    Info = 2,
+    Error = 4,
+}
+
+/// `repr` alone does not change how serde writes the enum
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+#[repr(u8)]
+pub enum Shade {
+    Light = 1,
+    Dark = 2,
+}
+
+#[derive(Serialize, Deserialize, TypeScriptStrict)]
+pub struct Ticket {
+    pub role: Role,
+    pub priority: Priority,
+    pub status: Status,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_dto;
+
+    #[test]
+    fn unit_enums_are_string_unions() {
+        let content = render_dto::<Ticket>("Role");
+
+        assert!(content.contains("export type Role = \"Admin\" | \"member\"\n"), "{}", content);
+        assert!(content.contains("export const RoleValues = [\"Admin\", \"member\"] as const"), "{}", content);
+        assert!(!content.contains("export type Admin"), "{}", content);
+    }
+
+    #[test]
+    fn labels_are_exported_for_every_value() {
+        let content = render_dto::<Ticket>("Role");
+
+        assert!(content.contains("export const RoleLabels: Record<Role, string> = {\n\tAdmin: \"Administrator\",\n\tmember: \"member\",\n}"), "{}", content);
+
+        let content = render_dto::<Level>("Level");
+
+        assert!(content.contains("\t2: \"Information\","), "{}", content);
+    }
+
+    #[test]
+    fn unit_enums_can_be_typescript_enums() {
+        let content = render_dto::<Ticket>("Priority");
+
+        assert!(content.contains("/** Priority of a ticket */\nexport enum Priority {\n\tLow = \"Low\",\n\t/** Needs attention this week */\n\tHigh = \"High\",\n}"), "{}", content);
+        assert!(content.contains("export const PriorityValues = [Priority.Low, Priority.High] as const"), "{}", content);
+    }
+
+    #[test]
+    fn unit_enums_can_be_const_objects() {
+        let content = render_dto::<Ticket>("Status");
+
+        assert!(content.contains("export const Status = {\n\t\"in-progress\": \"in-progress\",\n\tdone: \"done\",\n} as const"), "{}", content);
+        assert!(content.contains("export type Status = typeof Status[keyof typeof Status]"), "{}", content);
+        assert!(content.contains("export const StatusValues = [Status[\"in-progress\"], Status.done] as const"), "{}", content);
+    }
+
+    #[test]
+    fn integer_repr_enums_are_numeric_unions() {
+        let content = render_dto::<Level>("Level");
+
+        assert!(content.contains("export type Level = 1 | 2 | 4\n"), "{}", content);
+        assert!(content.contains("export const LevelValues = [1, 2, 4] as const"), "{}", content);
+    }
+
+    #[test]
+    fn repr_enums_match_their_serialized_values() {
+        assert_eq!(serde_json::to_string(&[Level::Debug, Level::Info, Level::Error]).unwrap(), "[1,2,4]");
+        assert_eq!(serde_json::to_string(&[Shade::Light, Shade::Dark]).unwrap(), "[\"Light\",\"Dark\"]");
+
+        let content = render_dto::<Shade>("Shade");
+
+        assert!(content.contains("export type Shade = \"Light\" | \"Dark\"\n"), "{}", content);
+    }
+}
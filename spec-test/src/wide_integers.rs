@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use tsclient::{TypeScriptStrict, prelude::*};
+
+#[derive(TypeScriptStrict)]
+pub struct Account {
+    pub id: u64,
+    pub balance: i128,
+    pub age: u8,
+    /// What `#[serde_as(as = "DisplayFromStr")]` expands to
+    #[serde(with = "::serde_with::As::<::serde_with::DisplayFromStr>")]
+    pub external_id: u64,
+    /// Wrapped adapters are not plain strings, they keep the type of the field
+    #[serde(with = "::serde_with::As::<Option<::serde_with::DisplayFromStr>>")]
+    pub parent_id: Option<u32>,
+    #[serde(with = "::serde_with::As::<Vec<::serde_with::DisplayFromStr>>")]
+    pub tag_ids: Vec<u32>,
+    pub limits: HashMap<u64, u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use tsclient::{ExportOptions, WideIntegers, types::builder::{GlobalTypeRegistry, TypeBuilder}};
+
+    use super::*;
+
+    fn build(policy: WideIntegers) -> TypeBuilder {
+        let mut registry = GlobalTypeRegistry::new();
+        Account::get_definition(&mut registry);
+
+        TypeBuilder::build_with_options(&registry, &ExportOptions::new().wide_integers(policy))
+    }
+
+    fn content(builder: &TypeBuilder) -> String {
+        builder.file_map.get("Account").expect("Type was not exported").lock().unwrap().content.clone()
+    }
+
+    #[test]
+    fn number_by_default_with_warnings() {
+        let builder = build(WideIntegers::default());
+        let content = content(&builder);
+
+        assert!(content.contains("\tid: number;"), "{}", content);
+        assert!(content.contains("\tbalance: number;"), "{}", content);
+        assert_eq!(builder.warnings.len(), 2, "{:?}", builder.warnings);
+        assert!(builder.warnings.iter().any(|w| w.contains("`u64`")), "{:?}", builder.warnings);
+        assert!(builder.warnings.iter().any(|w| w.contains("`i128`")), "{:?}", builder.warnings);
+    }
+
+    #[test]
+    fn bigint() {
+        let builder = build(WideIntegers::BigInt);
+        let content = content(&builder);
+
+        assert!(content.contains("\tid: bigint | number;"), "{}", content);
+        assert!(content.contains("\tbalance: bigint | number;"), "{}", content);
+        assert!(content.contains("\tage: number;"), "{}", content);
+        assert!(builder.warnings.is_empty(), "{:?}", builder.warnings);
+    }
+
+    #[test]
+    fn map_keys_are_strings() {
+        for policy in [WideIntegers::Number, WideIntegers::BigInt, WideIntegers::String] {
+            let content = content(&build(policy));
+
+            assert!(content.contains("\tlimits: Record<string, number>"), "{}", content);
+            assert_eq!(HashMap::<u64, i64>::ts_name(policy), format!("Record<string, {}>", policy.ts_type()));
+        }
+    }
+
+    #[test]
+    fn string() {
+        let builder = build(WideIntegers::String);
+        let content = content(&builder);
+
+        assert!(content.contains("\tid: string;"), "{}", content);
+        assert!(content.contains("\tbalance: string;"), "{}", content);
+        assert!(content.contains("\tage: number;"), "{}", content);
+    }
+
+    #[test]
+    fn ts_name_follows_the_policy() {
+        assert_eq!(u64::ts_name(WideIntegers::Number), "number");
+        assert_eq!(i128::ts_name(WideIntegers::String), "string");
+        assert_eq!(Vec::<Option<u64>>::ts_name(WideIntegers::BigInt), "Array<bigint | number | null>");
+        assert_eq!(u8::ts_name(WideIntegers::String), "number");
+    }
+
+    #[test]
+    fn render_and_diff_return_the_warnings() {
+        let router = tsclient::axum::Router::<()>::new().add_type::<Account>();
+
+        let rendered = router.api.render(&ExportOptions::new()).unwrap();
+        assert!(rendered.warnings.iter().any(|w| w.contains("`u64`")), "{:?}", rendered.warnings);
+
+        let diff = router.api.diff(&crate::temp_dir("wide-integers"), &ExportOptions::new()).unwrap();
+        assert_eq!(diff.warnings, rendered.warnings);
+    }
+
+    #[test]
+    fn display_from_str_fields_are_strings() {
+        let content = content(&build(WideIntegers::Number));
+
+        assert!(content.contains("\texternal_id: string"), "{}", content);
+        assert!(content.contains("\tparent_id: number | null;"), "{}", content);
+        assert!(content.contains("\ttag_ids: Array<number>"), "{}", content);
+    }
+}
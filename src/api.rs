@@ -1,17 +1,35 @@
-use std::{collections::HashMap, sync::Mutex, path::{PathBuf}, error::Error, fs, convert::Infallible};
+use std::{collections::{BTreeMap, HashMap, HashSet}, sync::{Arc, Mutex}, path::{Path, PathBuf}, error::Error, fs, convert::Infallible};
 
 use axum::{http::method, routing::{MethodRouter, MethodFilter}, handler::Handler, body::HttpBody};
 use regex::Regex;
 
-use crate::{types::{builder::{GlobalTypeRegistry, TypeBuilder}, model::Component}, Postion, api_router::RouteComponentType, utils::{clean_var_name, capitalize_first_letter}, FILE_HEADER};
+use crate::{types::{builder::{GlobalTypeRegistry, TypeBuilder}, model::{Component, WideIntegers}, ExportedType, TypescriptType}, Postion, api_router::RouteComponentType, utils::{clean_var_name, file_name}, export::{join_module, read_manifest, relative_import, write_files, ExportDiff, ExportOptions}};
 
 pub struct ClientObjectBuilder {
     obj: ClientObject,
-    import_map: HashMap<String, Vec<String>>
+    import_map: BTreeMap<String, Vec<String>>,
+    /// The function names taken in every module, routes sharing a module can't share a name
+    function_names: HashMap<String, HashSet<String>>,
 }
 
 impl ClientObjectBuilder {
-    pub(crate) fn add(&mut self, segments: Vec<&str>, method: &str) {
+    /// `name`, numbered when it is already taken in `module`
+    pub(crate) fn function_name(&mut self, module: &str, name: String) -> String {
+        let taken = self.function_names.entry(module.to_owned()).or_default();
+
+        let mut unique = name.clone();
+        let mut i = 1;
+        while taken.contains(&unique) {
+            unique = format!("{}{}", name, i);
+            i += 1;
+        }
+        taken.insert(unique.clone());
+
+        unique
+    }
+
+    /// Adds the function `method` to the client, as `key` in the object of the route
+    pub(crate) fn add(&mut self, segments: Vec<&str>, key: &str, method: &str) {
         let path = segments.join("/");
 
         if self.import_map.contains_key(&format!("./{}", path)) {
@@ -20,7 +38,7 @@ impl ClientObjectBuilder {
             self.import_map.insert(format!("./{}", path), vec![method.to_owned()]);
         }
 
-        self.obj.add(&segments, method.to_owned())
+        self.obj.add(&segments, key, method.to_owned())
     }
 
     pub(crate) fn get_ts_imports(&self) -> String {
@@ -32,16 +50,17 @@ impl ClientObjectBuilder {
 }
 
 pub enum ClientObject {
-    Obj(HashMap<String, ClientObject>),
+    /// Entries in the order their routes were declared
+    Obj(Vec<(String, ClientObject)>),
     Method(String)
 }
 
 impl ClientObject {
-    fn add(&mut self, segments: &[&str], method: String) {
+    fn add(&mut self, segments: &[&str], key: &str, method: String) {
         if segments.len() == 0 {
             match self {
                 ClientObject::Obj(fields) => {
-                    fields.insert(method.clone(), ClientObject::Method(method));
+                    fields.push((key.to_owned(), ClientObject::Method(method)));
                 },
                 ClientObject::Method(_) => todo!(),
             }
@@ -57,11 +76,12 @@ impl ClientObject {
 
         match self {
             ClientObject::Obj(fields) => {
-                if let Some(field) = fields.get_mut(segments[0]) {
-                    field.add(next, method);
+                if let Some((_, field)) = fields.iter_mut().find(|(name, _)| name == segments[0]) {
+                    field.add(next, key, method);
                 } else {
-                    fields.insert(segments[0].to_owned(), ClientObject::Obj(HashMap::new()));
-                    fields.get_mut(segments[0]).unwrap().add(next, method);
+                    let mut field = ClientObject::Obj(Vec::new());
+                    field.add(next, key, method);
+                    fields.push((segments[0].to_owned(), field));
                 }
             },
             ClientObject::Method(_) => panic!("Bad implementation"),
@@ -90,123 +110,216 @@ impl ClientObject {
     }
 }
 
+/// Generated sources by path relative to the export folder
+pub type RenderedFiles = BTreeMap<PathBuf, String>;
+
+/// What an export would write, see `Api::render`
+#[derive(Clone, Debug, Default)]
+pub struct Rendered {
+    pub files: RenderedFiles,
+    /// Problems with the exported types, like integers that lose precision in JavaScript
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug)]
 pub struct Api {
-    pub components: &'static once_cell::sync::Lazy<Mutex<GlobalTypeRegistry>>,
-    pub routes: HashMap<String, Route>
+    /// Owned by the router, or shared with every router using the global registry
+    pub components: Arc<Mutex<GlobalTypeRegistry>>,
+    /// Routes in the order they were declared
+    pub routes: Vec<(String, Route)>,
+    /// Types added with `add_type`, registered again when the router is nested or merged
+    pub types: Vec<ExportedType>,
 }
 
 impl Api {
-    pub fn export_to(&self, src_path: &PathBuf, remove_prefix: Option<&str>) -> Result<(), Box<dyn Error>> {
-        let mut client_builder = ClientObjectBuilder {
-            obj: ClientObject::Obj(HashMap::new()),
-            import_map: HashMap::new(),
+    /// Exports `T` with the route DTOs, for types no handler refers to
+    pub fn add_type<T: TypescriptType>(&mut self) -> &mut Self {
+        let exported = ExportedType::new::<T>();
+        (exported.register)(&mut self.components.lock().unwrap());
+        self.types.push(exported);
+
+        self
+    }
+
+    /// Shorthand for `export` with the default options
+    pub fn export_to(&self, src_path: &Path, remove_prefix: Option<&str>) -> Result<Vec<String>, Box<dyn Error>> {
+        let options = match remove_prefix {
+            Some(prefix) => ExportOptions::new().remove_prefix(prefix),
+            None => ExportOptions::new(),
         };
 
-        let dto_path = src_path.join("dto");
-        let api_path = src_path.join("api");
+        self.export(src_path, &options)
+    }
 
-        if !src_path.exists() {
-            fs::create_dir_all(&src_path)?;
-        }
+    /// Writes the files of `render` below `src_path` and returns its warnings. Only files whose content
+    /// changed are written, and the files of the previous export that are no longer generated are removed.
+    pub fn export(&self, src_path: &Path, options: &ExportOptions) -> Result<Vec<String>, Box<dyn Error>> {
+        let rendered = self.render(options)?;
 
-        if dto_path.exists() {
-            fs::remove_dir_all(&dto_path)?;
-        }
-        if !dto_path.exists() {
-            fs::create_dir_all(&dto_path)?;
-        }
+        fs::create_dir_all(src_path)?;
 
-        if api_path.exists() {
-            fs::remove_dir_all(&api_path)?;
-        }
-        if !api_path.exists() {
-            fs::create_dir_all(&api_path)?;
-        }
+        write_files(src_path, &rendered.files, &options.header)?;
+
+        Ok(rendered.warnings)
+    }
+
+    /// Compares the files below `src_path` to what `export` would write, nothing is written
+    pub fn diff(&self, src_path: &Path, options: &ExportOptions) -> Result<ExportDiff, Box<dyn Error>> {
+        let rendered = self.render(options)?;
 
-        let builder = {
-            let guard = self.components.lock()?;
+        let mut diff = ExportDiff::compare(src_path, &rendered.files, &read_manifest(src_path)?)?;
+        diff.warnings = rendered.warnings;
 
-            TypeBuilder::build(&*guard)
+        Ok(diff)
+    }
+
+    /// Fails with the `ExportDiff` when the files below `src_path` are not what `export` would write
+    pub fn check(&self, src_path: &Path, options: &ExportOptions) -> Result<(), Box<dyn Error>> {
+        let diff = self.diff(src_path, options)?;
+
+        match diff.is_empty() {
+            true => Ok(()),
+            false => Err(Box::new(diff)),
+        }
+    }
+
+    /// The generated files by path relative to the export folder, nothing is written to disk
+    pub fn render(&self, options: &ExportOptions) -> Result<Rendered, Box<dyn Error>> {
+        let mut client_builder = ClientObjectBuilder {
+            obj: ClientObject::Obj(Vec::new()),
+            import_map: BTreeMap::new(),
+            function_names: HashMap::new(),
         };
 
-        builder.export_to(&dto_path)?;
+        let types_path = PathBuf::from(options.types_dir());
 
-        for (path, route) in self.routes.iter() {
-            
-            let cleaned_path = match &remove_prefix {
-                Some(prefix) => match path.strip_prefix(prefix) {
-                    Some(x) => x,
-                    None => &path,
-                },
-                None => &path,
-            }.replace('{', "[")
-            .replace('}', "]");
+        let guard = self.components.lock().unwrap();
+        let builder = TypeBuilder::build_with_options(&guard, options);
+        let ctx = RouteContext { registry: &guard, builder: &builder, options };
 
-            let segments: Vec<_> = cleaned_path.split_terminator("/").filter(|x| !x.is_empty()).collect();
+        let mut files: RenderedFiles = builder.render()?.into_iter()
+            .map(|(path, content)| (types_path.join(path), content))
+            .collect();
 
-            let guard = self.components.lock()?;
+        if !options.client {
+            return Ok(Rendered { files, warnings: builder.warnings });
+        }
 
-            println!("{:#?}", segments);
+        let bigint = options.wide_integers == WideIntegers::BigInt;
+        let client_names = if bigint { "type ApiResult, bigintReviver, bigintReplacer" } else { "type ApiResult" };
 
-            let (dir, name, level) = match segments.len() {
-                0 => (api_path.clone(), "index.ts".to_owned(), 1),
-                1 => {
-                    if segments[0].split_whitespace().count() == 0 {
-                        (api_path.clone(), "index.ts".to_owned(), 1)
-                    } else {
-                        (api_path.clone(), format!("{}.ts", segments[0]), 1)
-                    }
-                }
-                level => {
-                    let l = segments.len();
+        let bundled = options.layout.is_bundled();
+        let client_module = options.client_module();
 
-                    let p = segments[0..(l - 1)].join("/");
-                    let wd = api_path.join(&p);
-                    (wd.clone(), format!("{}.ts", segments[l-1]), level)
-                }
+        // The imports and functions of every module with routes
+        let mut route_modules = BTreeMap::<String, (BTreeMap<String, String>, Vec<String>)>::new();
+
+        for (path, route) in self.routes.iter() {
+            
+            let cleaned_path = options.strip_prefix(path)
+                .replace('{', "[")
+                .replace('}', "]");
+
+            let segments: Vec<String> = cleaned_path.split_terminator("/")
+                .filter(|x| !x.trim().is_empty())
+                .map(file_name)
+                .collect();
+            let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+            // The route file below the api folder, the root route is `index`
+            let below_api = match segments.is_empty() {
+                true => String::from("index"),
+                false => segments.join("/"),
+            };
+            let name = format!("{}.ts", below_api.rsplit('/').next().unwrap_or_default());
+
+            let module = match bundled {
+                true => client_module.clone(),
+                false => join_module(&[&options.api_dir, &below_api]),
             };
 
-            let file = dir.join(&name);
+            let (imports, content) = route.build(&mut client_builder, path, segments, &name, &module, &ctx);
 
-            if !dir.exists() {
-                fs::create_dir_all(&dir)?;
+            let (module_imports, functions) = route_modules.entry(module).or_default();
+            if !bundled {
+                module_imports.insert("__client__".to_owned(), format!("import __client__, {{{}}} from \"{}\"", client_names, relative_import(&below_api, "client")));
             }
+            module_imports.extend(imports);
+            functions.push(content);
+        }
+
+        let (client_imports, client_functions) = match bundled {
+            true => {
+                let (imports, functions) = route_modules.remove(&client_module).unwrap_or_default();
+                (imports.into_values().collect::<Vec<_>>().join("\n"), functions.join("\n\n"))
+            },
+            false => (client_builder.get_ts_imports(), String::new()),
+        };
 
-            println!("{:?}", file);
+        for (module, (imports, functions)) in route_modules {
+            let imports = imports.into_values().collect::<Vec<_>>().join("\n");
+            let content = format!("{}\n{}\n\n {}", options.header, imports, functions.join("\n\n"));
 
-            fs::write(file, route.build(&mut client_builder, path, segments, &name, level, &*guard, &builder))?;
+            files.insert(PathBuf::from(format!("{}.ts", module)), options.reindent(&content));
         }
 
-        let mut client = String::from(FILE_HEADER);
+        // A single file starts with the types, they were rendered into the client module
+        let client_path = PathBuf::from(format!("{}.ts", client_module));
+        let mut client = files.remove(&client_path).unwrap_or_else(|| options.header.clone());
 
         client += &format!(r#"
-{}
+{imports}
 
-class Client {{
-    BASE_PATH = "";
+class {class} {{
+	BASE_PATH = "";
 
-    API = {}
+	API = {api}
 
-    setBasePath(path: string) {{
-        this.BASE_PATH = path;
-    }}
+	setBasePath(path: string) {{
+		this.BASE_PATH = path;
+	}}
 }}
 
-const client = new Client();
+const client = new {class}();
 
 export default client;
 
 export type ApiResult<T, E> = {{ok: true, value: T}} | {{ok: false, status: number,  error: E}};
-        "#, client_builder.get_ts_imports(), client_builder.obj.to_ts("\t".to_string()));
+        "#, imports = client_imports, class = options.client_class, api = client_builder.obj.to_ts("\t".to_string()));
+
+        if bigint {
+            client += BIGINT_JSON;
+        }
 
-        fs::write(api_path.join("client.ts"), client)?;
+        if bundled {
+            client += "\nconst __client__ = client;\n";
+            client += &client_functions;
+        }
+
+        files.insert(client_path, options.reindent(&client));
 
-        Ok(())
+        Ok(Rendered { files, warnings: builder.warnings })
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Copy, Clone)]
+/// Reads integers JavaScript numbers can't hold as `bigint`s and writes `bigint`s back as JSON numbers
+const BIGINT_JSON: &str = r#"
+export function bigintReviver(key: string, value: any, context?: {source?: string}): any {
+	if (typeof value === "number" && !Number.isSafeInteger(value) && context?.source !== undefined && /^-?\d+$/.test(context.source)) {
+		return BigInt(context.source);
+	}
+	return value;
+}
+
+export function bigintReplacer(key: string, value: any): any {
+	if (typeof value === "bigint") {
+		return (JSON as any).rawJSON(value.toString());
+	}
+	return value;
+}
+"#;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
 pub enum HTTPMethod {
     GET,
     POST,
@@ -260,7 +373,7 @@ impl HTTPMethod {
 
 #[derive(Debug)]
 pub struct Route {
-    pub methods: HashMap<HTTPMethod, Method>
+    pub methods: BTreeMap<HTTPMethod, Method>
 }
 
 #[derive(Default)]
@@ -270,6 +383,14 @@ pub struct RouteDestructured {
     pub body: Option<String>
 }
 
+/// What every route of an export is built with
+pub struct RouteContext<'a> {
+    pub registry: &'a GlobalTypeRegistry,
+    /// The exported types, routes import them under the names they are exported as
+    pub builder: &'a TypeBuilder,
+    pub options: &'a ExportOptions,
+}
+
 impl Route {
     pub fn rename_ts_methods(&mut self, map: HashMap<HTTPMethod, String>) {
         for (k, v) in map.into_iter() {
@@ -280,23 +401,26 @@ impl Route {
         }
     }
 
-    fn adjust_route_obj(&self, route: &mut RouteDestructured, part: &RouteComponentType, name: &str, full_route: &str, comp: &Component, registry: &GlobalTypeRegistry) {
+    fn adjust_route_obj(&self, route: &mut RouteDestructured, part: &RouteComponentType, name: &str, comp: &Component, ctx: &RouteContext) {
         match part {
             RouteComponentType::Query(_) => {
                 let query = format!(r#"
-    const __params = new URLSearchParams();
+	const __params = new URLSearchParams();
 
 {}
 
-    const __queryString = "?" + __params.toString();
+	const __queryString = "?" + __params.toString();
 "#,         
-                comp.typ.build_query_string(name, registry));
+                comp.shape(Postion::Body).build_query_string(name, ctx.registry));
 
                 route.query = Some(query);
             },
             RouteComponentType::Path(_) => {
                 panic!("Unsupported atm.")
             },
+            RouteComponentType::Json(Postion::Body, _) if ctx.options.wide_integers == WideIntegers::BigInt => {
+                route.body = Some(format!("const __body = JSON.stringify({}, bigintReplacer);", name))
+            },
             RouteComponentType::Json(Postion::Body, _) => {
                 route.body = Some(format!("const __body = JSON.stringify({});", name))
             },
@@ -307,16 +431,15 @@ impl Route {
         }
     }
 
-    pub fn build(&self, client_builder: &mut ClientObjectBuilder, route: &str, segments: Vec<&str>, name: &str, level: usize, registry: &GlobalTypeRegistry, builder: &TypeBuilder) -> String {
-        let mut imports = HashMap::new();
+    /// The functions of the route and their imports by name. `module` is the file they are
+    /// written to, relative to the export folder.
+    pub fn build(&self, client_builder: &mut ClientObjectBuilder, route: &str, segments: Vec<&str>, name: &str, module: &str, ctx: &RouteContext) -> (BTreeMap<String, String>, String) {
+        let RouteContext { registry, builder, options } = *ctx;
+        let mut imports = BTreeMap::new();
         let mut file_content = Vec::<String>::new();
 
-        let mut client_imp = (0..(level-1)).map(|_|"..").collect::<Vec<_>>().join("/");
-        if client_imp.is_empty() {
-            client_imp += ".";
-        }
-        
-        imports.insert("__client__".to_owned(), format!("import __client__, {{type ApiResult}} from \"{}/client\"", client_imp));
+        let bigint = options.wide_integers == WideIntegers::BigInt;
+        let parse_json = if bigint { "JSON.parse(await __result.text(), bigintReviver);" } else { "await __result.json();" };
 
         for (http, method) in self.methods.iter() {
             let mut route_inputs_builder = Vec::new();
@@ -330,42 +453,33 @@ impl Route {
                 let indexed = content.get_indexed();
                 let main_component = registry.get_indexed(indexed);
 
-                let clean_name = clean_var_name(&main_component.get_ts_name(registry));
+                let clean_name = clean_var_name(&main_component.get_ts_name(registry, options.wide_integers, Postion::Result));
 
-                self.adjust_route_obj(&mut route_obj, content, &clean_name, route, &main_component, registry);
+                self.adjust_route_obj(&mut route_obj, content, &clean_name, main_component, ctx);
 
-                let component = match content {
+                match content {
                     RouteComponentType::Query(_) |
                     RouteComponentType::Path(_)  |
                     RouteComponentType::Json(Postion::Body, _) |
                     RouteComponentType::Raw(Postion::Body, _) => {
-                        let (typ, _) = builder.get_type_and_import(&main_component.name, main_component.hash, level);
+                        let typ = main_component.client_type(registry, builder, module, &mut imports, Postion::Body);
                         route_inputs_builder.push(format!("{}: {}", clean_name, typ));
                         route_inputs_names.push(clean_name.clone());
-                        main_component.get_import_component(registry, Postion::Body)
                     },
                     
                     RouteComponentType::Raw(Postion::Result, _) |
                     RouteComponentType::Json(Postion::Result, _) => {
-                        route_result_builder = format!("Promise<{}>", main_component.get_client_result(registry));    
-                        main_component.get_import_component(registry, Postion::Result)   
+                        route_result_builder = format!("Promise<{}>", main_component.get_client_result(registry, builder, module, &mut imports));    
                     },
                 };
-
-                if let Some(comps) = component {
-                    for comp in comps {
-                        let (typ, import) = builder.get_type_and_import(&comp.name, comp.hash, level);
-                        if import != "" {
-                            imports.insert(typ, import);
-                        }
-                    }
-                }
             }
 
             let method_base_name = clean_var_name( &method.name.clone()
-                .unwrap_or(format!("{}{}", http.to_name(), capitalize_first_letter(name.strip_suffix(".ts").unwrap_or(name)))));
+                .unwrap_or(options.method_naming.name(http.to_name(), name.strip_suffix(".ts").unwrap_or(name))));
+            let client_key = method_base_name.clone();
+            let method_base_name = client_builder.function_name(module, method_base_name);
 
-            client_builder.add(segments.clone(), &method_base_name);
+            client_builder.add(segments.clone(), &client_key, &method_base_name);
 
             let inputs = route_inputs_builder.join(", ");
             let input_names = route_inputs_names.join(", ");
@@ -384,24 +498,25 @@ impl Route {
             let query = route_obj.query.unwrap_or(String::from("const __queryString = \"\";"));
 
             file_content.push(format!(r#"
-export async function {method_base_name}_RAW({inputs}): Promise<Response> {{
-    const headers = new Headers({{
-        {headers}
-    }});
+{raw_export}async function {method_base_name}_RAW({inputs}): Promise<Response> {{
+	const headers = new Headers({{
+		{headers}
+	}});
 
-    {body_def}
+	{body_def}
 
-    {query_def}
+	{query_def}
 
-    let __result = await fetch(`${{__client__.BASE_PATH}}{route}${{__queryString}}`, {{
-        method: '{http_method_cap}',
-        headers: headers,
-        body: __body
-    }});
+	let __result = await fetch(`${{__client__.BASE_PATH}}{route}${{__queryString}}`, {{
+		method: '{http_method_cap}',
+		headers: headers,
+		body: __body
+	}});
 
-    return __result
+	return __result
 }}
 "#, 
+                raw_export = if options.raw_functions { "export " } else { "" },
                 method_base_name = method_base_name, 
                 headers = headers,
                 body_def = body,
@@ -413,49 +528,50 @@ export async function {method_base_name}_RAW({inputs}): Promise<Response> {{
             let extract_body = if route_result_builder.starts_with("Promise<ApiResult<null,") {
                 "null;"
             } else {
-                "await __result.json();"
+                parse_json
             };
             
             file_content.push(format!(r#"
 export async function {method_base_name}({inputs}): {result} {{
-    let __result = await {method_base_name}_RAW({imput_names});
-
-    if(!__result.ok) {{
-        let error = await __result.json();
-        return {{
-            ok: false, 
-            status: __result.status,  
-            error
-        }}
-    }} else {{
-        let value = {extract_body}
-        return {{
-            ok: true,
-            value
-        }}
-    }}
+	let __result = await {method_base_name}_RAW({imput_names});
+
+	if(!__result.ok) {{
+		let error = {parse_json}
+		return {{
+			ok: false, 
+			status: __result.status,  
+			error
+		}}
+	}} else {{
+		let value = {extract_body}
+		return {{
+			ok: true,
+			value
+		}}
+	}}
 }}
 "#, 
                 method_base_name = method_base_name, 
                 inputs = inputs, 
                 imput_names = input_names,
                 result = route_result_builder,
-                extract_body = extract_body
+                extract_body = extract_body,
+                parse_json = parse_json
             )) 
 
         }
 
 
 
-        let imports = imports.into_iter().map(|(_, x)| x).collect::<Vec<_>>().join("\n");
-
-
-        format!("{}\n{}\n\n {}", FILE_HEADER, imports, file_content.join("\n\n"))
+        (imports, file_content.join("\n\n"))
     }
 }
 
 #[derive(Debug)]
 pub struct Method {
+    /// Indexes into the registry of the router the method was added to
     pub content: Vec<RouteComponentType>,
-    pub name: Option<String>
+    pub name: Option<String>,
+    /// Registers the handler's types, again for every router the method ends up in
+    pub(crate) build: fn(&mut GlobalTypeRegistry) -> Vec<RouteComponentType>,
 }
\ No newline at end of file
@@ -1,11 +1,11 @@
-use std::{convert::Infallible, collections::HashMap, future::Future};
+use std::{convert::Infallible, collections::HashMap, sync::{Arc, Mutex}};
 
 use axum::{Router, routing::{MethodRouter, IntoMakeService}, handler::Handler, response::IntoResponse, extract::{FromRequestParts, FromRequest, Query}, Json, body::{HttpBody, Body}, http::Request};
 use serde::Serialize;
 use tower_layer::Layer;
 use tower_service::Service;
 
-use crate::{api::{Api, Route, HTTPMethod, Method}, types::{model::ComponentReference, builder::{HasIndexed, GlobalTypeRegistry}, TypescriptType}, Postion, GLOBAL_TYPE_REGISTRY};
+use crate::{api::{Api, Route, HTTPMethod, Method}, types::{builder::{HasIndexed, GlobalTypeRegistry}, ExportedType, TypescriptType}, Postion, GLOBAL_TYPE_REGISTRY};
 
 pub trait IntoMethodRouter<S,B,E> {
     fn into_method_router(self) -> MethodRouter<S,B,E>;
@@ -30,25 +30,85 @@ B: HttpBody + Send + 'static, {
     pub router: Router<S, B>,
 }
 impl<S: Clone + Send + Sync + 'static, B: HttpBody + Send + 'static> ApiRouter<S,B> {
+    /// A router with its own type registry, only the types of its routes are exported
     pub fn new() -> Self {
+        Self::with_registry(Arc::new(Mutex::new(GlobalTypeRegistry::new())))
+    }
+
+    /// A router sharing the process wide registry, exports contain the types of every such router
+    pub fn with_global_registry() -> Self {
+        Self::with_registry(GLOBAL_TYPE_REGISTRY.clone())
+    }
+
+    fn with_registry(components: Arc<Mutex<GlobalTypeRegistry>>) -> Self {
         Self {
             api: Api {
-                components: &GLOBAL_TYPE_REGISTRY,
-                routes: HashMap::new(),
+                components,
+                routes: Vec::new(),
+                types: Vec::new(),
             },
             router: Router::new()
         }
     }
 
+    /// Exports `T` with the route DTOs, for types no handler refers to
+    pub fn add_type<T: TypescriptType>(mut self) -> Self {
+        self.api.add_type::<T>();
+
+        self
+    }
+
+    /// Exports every type marked with `#[ts(export)]` with the route DTOs, sorted by module path and name
+    pub fn add_exported_types(mut self) -> Self {
+        self.add_types(ExportedType::all());
+
+        self
+    }
+
+    /// Registers the types added with `add_type` to another router in this router's registry
+    fn add_types(&mut self, types: Vec<ExportedType>) {
+        {
+            let mut registry = self.api.components.lock().unwrap();
+            for exported in &types {
+                (exported.register)(&mut registry);
+            }
+        }
+
+        self.api.types.extend(types);
+    }
+
+    /// Registers the types of the route's methods in this router's registry
+    fn add_route(&mut self, path: String, mut route: Route) {
+        {
+            let mut registry = self.api.components.lock().unwrap();
+            for method in route.methods.values_mut() {
+                method.content = (method.build)(&mut registry);
+            }
+        }
+
+        self.api.routes.push((path, route));
+    }
+
     pub fn nest(mut self, prefix_route: &str, nested: ApiRouter<S, B>) -> Self {
         for (path, route) in nested.api.routes {
-            self.api.routes.insert(format!("{}{}", prefix_route, path), route);
+            self.add_route(format!("{}{}", prefix_route, path), route);
         }
+        self.add_types(nested.api.types);
         self.router = self.router.nest(prefix_route, nested.router);
 
         self
     }
 
+    pub fn merge(mut self, other: ApiRouter<S, B>) -> Self {
+        for (path, route) in other.api.routes {
+            self.add_route(path, route);
+        }
+        self.add_types(other.api.types);
+        self.router = self.router.merge(other.router);
+
+        self
+    }
+
     pub fn nest_service_without_api<T>(mut self, path: &str, service: T) -> Self
     where 
         T: Service<Request<B>, Error = Infallible> + Clone + Send + 'static,
@@ -60,7 +120,7 @@ impl<S: Clone + Send + Sync + 'static, B: HttpBody + Send + 'static> ApiRouter<S
     }
 
     pub fn route(mut self, route: &str, method: ApiMethodRouter<S, B>) -> Self {
-        self.api.routes.insert(route.to_owned(), method.route);
+        self.add_route(route.to_owned(), method.route);
         self.router = self.router.route(route, method.router);
 
         return self
@@ -139,14 +199,10 @@ impl<S: Clone + Send,B: HttpBody + Send + 'static> ApiMethodRouter<S,B,Infallibl
     H: Handler<T, S, B> + ApiBuildable<T>,
     T: 'static,
     S: Send + Sync + 'static, {
-        let mut content = {
-            let mut registry = GLOBAL_TYPE_REGISTRY.lock().unwrap();
-            H::build(&mut *registry)
-        };
-    
         let method = Method {
-            content,
-            name: None
+            content: Vec::new(),
+            name: None,
+            build: H::build,
         };
 
         self.route.methods.insert(http_method, method);
@@ -0,0 +1,415 @@
+use std::{collections::BTreeMap, error::Error, fmt, fs, io, path::{Component, Path, PathBuf}};
+
+use crate::{FILE_HEADER, types::{builder::TypeNaming, model::WideIntegers}, utils::capitalize_first_letter};
+
+/// How a client is written, read by every step of the export
+#[derive(Clone, Debug)]
+pub struct ExportOptions {
+    pub(crate) dto_dir: String,
+    pub(crate) api_dir: String,
+    pub(crate) remove_prefix: Option<String>,
+    pub(crate) header: String,
+    pub(crate) indent: String,
+    pub(crate) client_class: String,
+    pub(crate) method_naming: MethodNaming,
+    pub(crate) raw_functions: bool,
+    pub(crate) client: bool,
+    pub(crate) layout: Layout,
+    pub(crate) wide_integers: WideIntegers,
+    pub(crate) type_naming: TypeNaming,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            dto_dir: String::from("dto"),
+            api_dir: String::from("api"),
+            remove_prefix: None,
+            header: String::from(FILE_HEADER),
+            indent: String::from("\t"),
+            client_class: String::from("Client"),
+            method_naming: MethodNaming::default(),
+            raw_functions: true,
+            client: true,
+            layout: Layout::default(),
+            wide_integers: WideIntegers::default(),
+            type_naming: TypeNaming::default(),
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The folder the types are written to, `dto` by default
+    pub fn dto_dir(mut self, dir: impl Into<String>) -> Self {
+        self.dto_dir = dir.into();
+        self
+    }
+
+    /// The folder the route functions and `client.ts` are written to, `api` by default
+    pub fn api_dir(mut self, dir: impl Into<String>) -> Self {
+        self.api_dir = dir.into();
+        self
+    }
+
+    /// Stripped from the start of every route before it is turned into a file path
+    pub fn remove_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.remove_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Written at the top of every file, an empty header writes nothing
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    /// One level of indentation, a tab by default
+    pub fn indent(mut self, indent: impl Into<String>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    /// The name of the class `client.ts` exports an instance of, `Client` by default
+    pub fn client_class(mut self, name: impl Into<String>) -> Self {
+        self.client_class = name.into();
+        self
+    }
+
+    /// How route functions are named when they are not renamed with `rename_ts`
+    pub fn method_naming(mut self, naming: MethodNaming) -> Self {
+        self.method_naming = naming;
+        self
+    }
+
+    /// Whether the `_RAW` functions returning the plain `Response` are exported
+    pub fn raw_functions(mut self, export: bool) -> Self {
+        self.raw_functions = export;
+        self
+    }
+
+    /// Whether the route functions and `client.ts` are written, without them only the types are
+    pub fn client(mut self, export: bool) -> Self {
+        self.client = export;
+        self
+    }
+
+    /// How the types and route functions are split into files
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// How 64 and 128 bit integers are written, `WideIntegers::Number` by default
+    pub fn wide_integers(mut self, policy: WideIntegers) -> Self {
+        self.wide_integers = policy;
+        self
+    }
+
+    /// How derived types sharing a name are told apart, `TypeNaming::Numbered` by default
+    pub fn type_naming(mut self, naming: TypeNaming) -> Self {
+        self.type_naming = naming;
+        self
+    }
+
+    /// The folder the type modules are in, relative to the export folder
+    pub(crate) fn types_dir(&self) -> &str {
+        match self.layout {
+            Layout::PerType | Layout::PerModule => &self.dto_dir,
+            Layout::Bundled | Layout::SingleFile => "",
+        }
+    }
+
+    /// The module with the `Client` class, relative to the export folder
+    pub(crate) fn client_module(&self) -> String {
+        match self.layout {
+            Layout::PerType | Layout::PerModule => join_module(&[&self.api_dir, "client"]),
+            Layout::Bundled | Layout::SingleFile => String::from("client"),
+        }
+    }
+
+    pub(crate) fn strip_prefix<'a>(&self, path: &'a str) -> &'a str {
+        match self.remove_prefix.as_deref() {
+            Some(prefix) => path.strip_prefix(prefix).unwrap_or(path),
+            None => path,
+        }
+    }
+
+    /// The generated code is indented with tabs, every leading tab becomes one level of `indent`
+    pub(crate) fn reindent(&self, content: &str) -> String {
+        if self.indent == "\t" {
+            return content.to_string();
+        }
+
+        content.split('\n')
+            .map(|line| {
+                let code = line.trim_start_matches('\t');
+                self.indent.repeat(line.len() - code.len()) + code
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// How the generated code is split into files. Rendering fails with a `NameCollision` when types
+/// written to the same file export the same name, like variant aliases of two enums
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Layout {
+    /// `dto/<Type>.ts` for every type and `api/<route>.ts` for every route
+    #[default]
+    PerType,
+    /// `dto/<rust/module/path>.ts` with every type of a Rust module, types of the crate root and
+    /// library types go into `dto/index.ts`. Routes are written like `PerType`
+    PerModule,
+    /// `types.ts` with every type and `client.ts` with the client and every route function
+    Bundled,
+    /// `client.ts` with everything, it imports nothing
+    SingleFile,
+}
+
+impl Layout {
+    /// Whether all route functions are written to the client module
+    pub(crate) fn is_bundled(&self) -> bool {
+        matches!(self, Layout::Bundled | Layout::SingleFile)
+    }
+}
+
+/// Joins the non-empty parts of a module path with `/`
+pub(crate) fn join_module(parts: &[&str]) -> String {
+    parts.iter().filter(|part| !part.is_empty()).copied().collect::<Vec<_>>().join("/")
+}
+
+/// The import path of the module `to` in the module `from`, both relative to the same folder
+pub(crate) fn relative_import(from: &str, to: &str) -> String {
+    match from.matches('/').count() {
+        0 => format!("./{}", to),
+        depth => format!("{}/{}", vec![".."; depth].join("/"), to),
+    }
+}
+
+/// How route functions are named, from the HTTP method and the last segment of the route
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MethodNaming {
+    /// `getOrders`
+    #[default]
+    CamelCase,
+    /// `get_orders`
+    SnakeCase,
+}
+
+impl MethodNaming {
+    pub(crate) fn name(&self, method: &str, segment: &str) -> String {
+        match self {
+            MethodNaming::CamelCase => format!("{}{}", method, capitalize_first_letter(segment)),
+            MethodNaming::SnakeCase => format!("{}_{}", method, segment),
+        }
+    }
+}
+
+/// How the files on disk differ from what an export would write, see `Api::diff`
+#[derive(Clone, Debug, Default)]
+pub struct ExportDiff {
+    /// Files the export would create
+    pub missing: Vec<PathBuf>,
+    /// Files the export would overwrite with different content
+    pub changed: Vec<ChangedFile>,
+    /// Files the export would delete
+    pub extra: Vec<PathBuf>,
+    /// The warnings of the export, they do not make the files out of date
+    pub warnings: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ChangedFile {
+    pub path: PathBuf,
+    /// The first line that differs, starting at 1
+    pub line: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ExportDiff {
+    /// Compares rendered files to the files below `src_path`, `previous` are the files of the last export
+    pub(crate) fn compare(src_path: &Path, files: &BTreeMap<PathBuf, String>, previous: &[PathBuf]) -> io::Result<Self> {
+        let mut diff = Self::default();
+
+        for (path, expected) in files {
+            match fs::read_to_string(src_path.join(path)) {
+                Ok(found) if found == *expected => {},
+                Ok(found) => diff.changed.push(ChangedFile::new(path.clone(), expected, &found)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => diff.missing.push(path.clone()),
+                Err(e) => return Err(e),
+            }
+        }
+
+        for path in previous {
+            if !files.contains_key(path) && src_path.join(path).exists() {
+                diff.extra.push(path.clone());
+            }
+        }
+
+        Ok(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.changed.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl ChangedFile {
+    fn new(path: PathBuf, expected: &str, found: &str) -> Self {
+        let mut expected_lines = expected.split('\n');
+        let mut found_lines = found.split('\n');
+        let mut line = 1;
+
+        loop {
+            match (expected_lines.next(), found_lines.next()) {
+                (Some(e), Some(f)) if e == f => line += 1,
+                (e, f) => return Self {
+                    path,
+                    line,
+                    expected: e.unwrap_or_default().to_string(),
+                    found: f.unwrap_or_default().to_string(),
+                },
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExportDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The exported client is out of date, export it again")?;
+
+        for path in &self.missing {
+            write!(f, "\n  missing: {}", path.display())?;
+        }
+        for file in &self.changed {
+            write!(f, "\n  changed: {} (line {})", file.path.display(), file.line)?;
+            write!(f, "\n    expected: {}", file.expected.trim())?;
+            write!(f, "\n    found:    {}", file.found.trim())?;
+        }
+        for path in &self.extra {
+            write!(f, "\n  extra: {}", path.display())?;
+        }
+        for warning in &self.warnings {
+            write!(f, "\n  warning: {}", warning)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ExportDiff {}
+
+/// Lists the files written by the last export, only these are removed when they are no longer generated
+pub(crate) const MANIFEST: &str = ".tsclient-manifest";
+
+/// Why an export stopped before writing anything
+#[derive(Clone, Debug)]
+pub enum ExportError {
+    /// A generated path that would end up outside the export folder
+    UnsafePath(PathBuf),
+    /// A file that is neither in the manifest nor starts with the header, it was probably not generated
+    NotGenerated(PathBuf),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::UnsafePath(path) => write!(f, "`{}` is not a path inside the export folder", path.display()),
+            ExportError::NotGenerated(path) => write!(f, "`{}` was not generated and would be overwritten, is the export folder right?", path.display()),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+/// The files of the last export below `src_path`, empty when there is no manifest
+pub(crate) fn read_manifest(src_path: &Path) -> io::Result<Vec<PathBuf>> {
+    match fs::read_to_string(src_path.join(MANIFEST)) {
+        Ok(content) => Ok(content.lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(PathBuf::from)
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn manifest_content(files: &BTreeMap<PathBuf, String>) -> String {
+    let mut content = String::from("# Generated by tsclient, these files are removed again when they are no longer generated\n");
+    for path in files.keys() {
+        let segments: Vec<_> = path.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+        content += &format!("{}\n", segments.join("/"));
+    }
+    content
+}
+
+/// Relative paths without `..`, so they stay inside the folder they are joined to
+fn check_path(path: &Path) -> Result<(), ExportError> {
+    match path.components().all(|c| matches!(c, Component::Normal(_))) && path.components().next().is_some() {
+        true => Ok(()),
+        false => Err(ExportError::UnsafePath(path.to_path_buf())),
+    }
+}
+
+/// Writes the files whose content changed and removes the files of the last export that are
+/// no longer generated. Files that were not generated are never overwritten or removed.
+pub(crate) fn write_files(src_path: &Path, files: &BTreeMap<PathBuf, String>, header: &str) -> Result<(), Box<dyn Error>> {
+    let previous = read_manifest(src_path)?;
+
+    // Everything is checked before the first write
+    for (path, content) in files {
+        check_path(path)?;
+
+        let generated = previous.contains(path);
+        match fs::read(src_path.join(path)) {
+            Ok(found) if found == content.as_bytes() || generated => {},
+            Ok(found) if !header.is_empty() && found.starts_with(header.as_bytes()) => {},
+            Ok(_) => return Err(Box::new(ExportError::NotGenerated(path.clone()))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+            Err(e) => return Err(Box::new(e)),
+        }
+    }
+    for path in &previous {
+        check_path(path)?;
+    }
+
+    for (path, content) in files {
+        let file = src_path.join(path);
+        if fs::read(&file).is_ok_and(|found| found == content.as_bytes()) {
+            continue;
+        }
+
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(file, content)?;
+    }
+
+    for path in previous.iter().filter(|path| !files.contains_key(*path)) {
+        let file = src_path.join(path);
+        if file.is_file() {
+            fs::remove_file(&file)?;
+        }
+
+        // Folders only the stale file was in, `remove_dir` leaves folders with other files alone
+        let mut dir = file.parent();
+        while let Some(d) = dir.filter(|d| *d != src_path) {
+            if fs::remove_dir(d).is_err() {
+                break;
+            }
+            dir = d.parent();
+        }
+    }
+
+    let manifest = manifest_content(files);
+    if fs::read_to_string(src_path.join(MANIFEST)).ok().as_deref() != Some(manifest.as_str()) {
+        fs::write(src_path.join(MANIFEST), manifest)?;
+    }
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::marker::PhantomData;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use once_cell::sync::Lazy;
 use serde::{Serialize, Deserialize};
@@ -12,6 +12,7 @@ pub mod types;
 #[cfg(not(target_family = "wasm"))]
 pub mod axum;
 pub mod prelude;
+pub mod export;
 #[cfg(not(target_family = "wasm"))]
 pub mod api;
 
@@ -27,8 +28,14 @@ use types::builder::GlobalTypeRegistry;
 use types::builder::HasIndexed;
 use types::model::Component;
 use types::model::Type;
+use types::model::EnumStyle;
+pub use types::model::WideIntegers;
+pub use types::builder::TypeNaming;
+pub use export::{ChangedFile, ExportDiff, ExportError, ExportOptions, Layout, MethodNaming};
 pub use typescript::TypeScript;
 pub use typescript::TypeScriptStrict;
+#[doc(hidden)]
+pub use inventory;
 
 #[derive(Debug, Copy, Clone)]
 pub enum Postion {
@@ -38,8 +45,9 @@ pub enum Postion {
 
 //TODO: Remove Hash and include TypeId
 
-static GLOBAL_TYPE_REGISTRY: Lazy<Mutex<GlobalTypeRegistry>> = Lazy::new(|| {
-    Mutex::new(GlobalTypeRegistry::new())
+/// Shared by routers created with `ApiRouter::with_global_registry`
+static GLOBAL_TYPE_REGISTRY: Lazy<Arc<Mutex<GlobalTypeRegistry>>> = Lazy::new(|| {
+    Arc::new(Mutex::new(GlobalTypeRegistry::new()))
 });
 
 pub(crate) const FILE_HEADER: &'static str = 
@@ -52,6 +60,7 @@ r#"/**
 
 pub struct AnyDef;
 
+/// Clears the global registry, routers with their own registry are not affected
 pub fn reset() {
     let mut registry = GLOBAL_TYPE_REGISTRY.lock().unwrap();
     registry.reset();
@@ -74,13 +83,19 @@ impl TypescriptType for T1 {
         registry.finalize(type_id, Component {
             name: String::from("T1"),
             typ: Type::SimpleType(String::from("T1")),
+            input: None,
+            generics: Vec::new(),
+            docs: None,
+            enum_style: EnumStyle::Union,
+            brand: false,
+            rust_path: None,
             hash,
         })
     }
     fn name() -> String {
         String::from("T1")
     }
-    fn ts_name() -> String {
+    fn ts_name(_integers: WideIntegers) -> String {
         String::from("T1")
     }
     fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
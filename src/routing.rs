@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use axum::handler::Handler;
 
-use crate::{api_router::{ApiMethodRouter, ApiBuildable}, api::{Method, HTTPMethod, Route}, GLOBAL_TYPE_REGISTRY};
+use crate::{api_router::{ApiMethodRouter, ApiBuildable}, api::{Method, HTTPMethod, Route}};
 
 
 
@@ -13,18 +13,14 @@ where
     T: 'static {
 
 
-    let mut content = {
-        let mut registry = GLOBAL_TYPE_REGISTRY.lock().unwrap();
-        H::build(&mut *registry)
-    };
-
     let method = Method {
-        content,
-        name: None
+        content: Vec::new(),
+        name: None,
+        build: H::build,
     };
     ApiMethodRouter {
         route: Route {
-            methods: HashMap::from([(http_method, method)])
+            methods: BTreeMap::from([(http_method, method)])
         },
         rename_map: HashMap::new(),
         header: HashMap::new(),
@@ -1,63 +1,217 @@
-use std::{collections::{HashMap, HashSet}, any::TypeId, sync::Mutex, path::PathBuf, error::Error, fs};
+use std::{collections::{BTreeMap, BTreeSet, HashMap, hash_map::DefaultHasher}, any::TypeId, hash::{Hash, Hasher}, sync::Mutex, path::{Path, PathBuf}, error::Error, fmt, fs};
 
-use crate::FILE_HEADER;
+use crate::{Postion, export::{join_module, relative_import, write_files, ExportOptions, Layout}, utils::capitalize_first_letter};
 
-use super::model::Component;
+use super::model::{Component, Type, WideIntegers};
 
 #[derive(Debug)]
 pub struct TypeBuilder {
-    pub file_map: HashMap::<String, Mutex<ComponentFileBuilder>>,
+    /// Files by name, sorted so they are always written in the same order
+    pub file_map: BTreeMap::<String, Mutex<ComponentFileBuilder>>,
     pub rename_map: HashMap::<u64, String>,
+    /// The dto subfolder of a file by hash, for `TypeNaming::Folders`
+    pub folder_map: HashMap::<u64, String>,
+    /// The Rust module of derived types by hash, for `Layout::PerModule`
+    pub module_map: HashMap::<u64, String>,
+    /// Problems with the exported types, like integers that lose precision in JavaScript
+    pub warnings: Vec<String>,
+    /// Types sharing a name that `TypeNaming` can't tell apart, the export fails with the first one
+    pub collisions: Vec<NameCollision>,
+    /// The arguments by parameter name while a generic type is inlined with `#[ts(inline)]`
+    pub(crate) type_args: HashMap<String, String>,
+    pub options: ExportOptions,
 }
 
 impl TypeBuilder {
     pub fn build(registry: &GlobalTypeRegistry) -> Self {
+        Self::build_with_options(registry, &ExportOptions::default())
+    }
+
+    pub fn build_with_options(registry: &GlobalTypeRegistry, options: &ExportOptions) -> Self {
         let mut builder = Self {
-            file_map: HashMap::<String, Mutex<ComponentFileBuilder>>::new(),
+            file_map: BTreeMap::<String, Mutex<ComponentFileBuilder>>::new(),
             rename_map: HashMap::<u64, String>::new(),
+            folder_map: HashMap::<u64, String>::new(),
+            module_map: HashMap::<u64, String>::new(),
+            warnings: Vec::new(),
+            collisions: Vec::new(),
+            type_args: HashMap::new(),
+            options: options.clone(),
         };
 
+        builder.name_derived_types(registry);
+
         for comp in &registry.components {
-            comp.build(&mut builder, registry);
+            comp.build(&mut builder, registry, Postion::Result);
+            comp.build(&mut builder, registry, Postion::Body);
+
+            if let (Type::WideInteger(rust_type), WideIntegers::Number) = (&comp.typ, options.wide_integers) {
+                builder.warnings.push(format!("`{}` is written as `number`, values above 2^53 lose precision. Use `WideIntegers::BigInt` or `WideIntegers::String` to keep them exact", rust_type));
+            }
         }
 
         return builder
     }
 
-    pub fn export_to(&self, dto_path: &PathBuf) -> Result<(), Box<dyn Error>> {
-        if !dto_path.exists() {
-            fs::create_dir_all(&dto_path)?;
+    /// Names the files of derived types sharing a TypeScript name according to the
+    /// export options' `TypeNaming`, before any file is started. Library types like tuples
+    /// are always numbered.
+    fn name_derived_types(&mut self, registry: &GlobalTypeRegistry) {
+        let naming = self.options.type_naming;
+
+        let mut by_name = BTreeMap::<String, Vec<(u64, &str)>>::new();
+        for comp in &registry.components {
+            let Some(rust_path) = comp.rust_path.as_deref() else { continue };
+
+            for pos in [Postion::Result, Postion::Body] {
+                if !matches!(comp.shape(pos), Type::Struct(_) | Type::Enum(_, _)) {
+                    continue;
+                }
+
+                let (name, hash) = comp.file_for(registry, pos);
+                let files = by_name.entry(name).or_default();
+                if !files.iter().any(|(h, _)| *h == hash) {
+                    files.push((hash, rust_path));
+                }
+
+                if naming == TypeNaming::Folders {
+                    self.folder_map.insert(hash, module_segments(rust_path).join("/"));
+                }
+                self.module_map.insert(hash, module_segments(rust_path).join("/"));
+            }
+        }
+
+        for (name, files) in by_name.into_iter().filter(|(_, files)| files.len() > 1) {
+            match naming {
+                TypeNaming::Numbered => {},
+                TypeNaming::Error => self.collisions.push(NameCollision {
+                    name,
+                    first: files[0].1.to_string(),
+                    second: files[1].1.to_string(),
+                }),
+                TypeNaming::ModulePath | TypeNaming::Folders => match distinct_module_prefixes(&files) {
+                    Ok(prefixes) => {
+                        for ((hash, _), prefix) in files.iter().zip(prefixes) {
+                            self.rename_map.insert(*hash, format!("{}{}", prefix, name));
+                        }
+                    },
+                    Err((first, second)) => self.collisions.push(NameCollision {
+                        name,
+                        first: files[first].1.to_string(),
+                        second: files[second].1.to_string(),
+                    }),
+                },
+            }
+        }
+    }
+
+    /// Writes the files of `render` below `dto_path` like `Api::export` does, files that were not
+    /// generated are never overwritten
+    pub fn export_to(&self, dto_path: &Path) -> Result<(), Box<dyn Error>> {
+        let files = self.render()?;
+
+        fs::create_dir_all(dto_path)?;
+        write_files(dto_path, &files, &self.options.header)
+    }
+
+    /// The type files by path below the folder of the type modules
+    pub fn render(&self) -> Result<BTreeMap<PathBuf, String>, NameCollision> {
+        if let Some(collision) = self.collisions.first() {
+            return Err(collision.clone());
         }
 
+        let mut modules = BTreeMap::<String, Vec<ComponentFileBuilder>>::new();
         for (name, file) in self.file_map.iter() {
-            let mut file_content = String::new();
-            file_content += FILE_HEADER;
+            modules.entry(self.module_of(name)).or_default().push(file.lock().expect("Poisened").clone());
+        }
+
+        let mut files = BTreeMap::new();
+
+        for (module, module_files) in modules {
+            let mut imports = Vec::new();
+            let mut type_defs = BTreeSet::new();
+            let mut content = String::new();
+            // Merged files can't export a name twice, e.g. the variant aliases of two enums
+            let mut exported_by = HashMap::<String, String>::new();
+
+            for file in module_files {
+                let owner = file.rust_path.clone().unwrap_or_else(|| file.name.clone());
+                for export in file.exports.iter() {
+                    if let Some(first) = exported_by.insert(export.clone(), owner.clone()) {
+                        return Err(NameCollision { name: export.clone(), first, second: owner });
+                    }
+                }
+
+                for (name, renamed) in file.imports.iter() {
+                    let target = self.module_of(name);
 
-            {
-                // Nasty but don't want to fight lifetimes atm.
-                let file_guard = file.lock().expect("Poisened");
+                    // Types of the same module are declared next to each other
+                    if target == module {
+                        if let Some(alias) = renamed {
+                            type_defs.insert(format!("type {} = {};", alias, name));
+                        }
+                        continue;
+                    }
 
-                for (name, renamed) in file_guard.imports.iter() {
                     let rename = match renamed {
                         Some(x) => format!(" as {}", x),
                         None => String::from(""),
                     };
-                    
-                    file_content += &format!("import {{type {}{}}} from \"./{}\";\n", name, rename, name);
-                }
 
-                for def in file_guard.type_defs.iter() {
-                    file_content += &format!("\n{}", def);
+                    let import = format!("import {{type {}{}}} from \"{}\";\n", name, rename, relative_import(&module, &target));
+                    if !imports.contains(&import) {
+                        imports.push(import);
+                    }
                 }
 
-                file_content += &format!("{}", file_guard.content);
+                type_defs.extend(file.type_defs);
+                content += &file.content;
+            }
+
+            let mut file_content = self.options.header.clone();
+            file_content += &imports.concat();
+
+            for def in type_defs.iter() {
+                file_content += &format!("\n{}", def);
             }
-            
-            
-            fs::write(dto_path.join(format!("{}.ts", name)), file_content)?;
+
+            file_content += &content;
+
+            files.insert(PathBuf::from(format!("{}.ts", module)), self.options.reindent(&file_content));
+        }
+
+        Ok(files)
+    }
+
+    /// The module a type is written to, relative to the folder of the type modules
+    pub fn module_of(&self, name: &str) -> String {
+        match self.options.layout {
+            Layout::PerType => self.file_path(name),
+            Layout::PerModule => {
+                let module = self.file_map.get(name)
+                    .and_then(|file| self.module_map.get(&file.lock().unwrap().hash).cloned())
+                    .unwrap_or_default();
+
+                match module.is_empty() {
+                    true => String::from("index"),
+                    false => module,
+                }
+            },
+            Layout::Bundled => String::from("types"),
+            Layout::SingleFile => String::from("client"),
         }
+    }
+
+    /// The path of a file below the dto folder, without extension
+    pub fn file_path(&self, name: &str) -> String {
+        let folder = self.file_map.get(name)
+            .map(|file| file.lock().unwrap().folder.clone())
+            .unwrap_or_default();
 
-        Ok(())
+        match folder.is_empty() {
+            true => name.to_string(),
+            false => format!("{}/{}", folder, name),
+        }
     }
 
     pub fn start_file(&mut self, name: &str, hash: u64) -> Option<&Mutex<ComponentFileBuilder>> {
@@ -85,25 +239,27 @@ impl TypeBuilder {
             None => name.to_string()
         };
 
-        let file_builder = ComponentFileBuilder::new(name.clone(), hash);
+        let mut file_builder = ComponentFileBuilder::new(name.clone(), hash);
+        file_builder.folder = self.folder_map.get(&hash).cloned().unwrap_or_default();
         self.file_map.insert(file_builder.name.clone(), Mutex::new(file_builder));
 
         return self.file_map.get(&name)
     }
 
-    pub fn get_type_and_import(&self, name: &str, hash: u64, level: usize) -> (String, String) {
+    /// The name of a type and how the module `from`, relative to the export folder, imports it
+    pub fn get_type_and_import(&self, name: &str, hash: u64, from: &str) -> (String, String) {
         let name = match self.rename_map.get(&hash) {
             Some(c) => c,
             None => name
         };
 
-        let levels = (0..level).map(|_| "..").collect::<Vec<_>>().join("/");
+        let module = join_module(&[self.options.types_dir(), &self.module_of(name)]);
 
         return match self.file_map.get(name) {
-            Some(_) => {
-                (name.to_string(), format!("import {{ type {} }} from \"{}/dto/{}\";", name, levels, name))
+            Some(_) if module != from => {
+                (name.to_string(), format!("import {{ type {} }} from \"{}\";", name, relative_import(from, &module)))
             }
-            None => (name.to_string(), String::from(""))
+            _ => (name.to_string(), String::from(""))
         };
     }
 
@@ -132,6 +288,83 @@ impl TypeBuilder {
     }
 }
 
+/// How derived types sharing a TypeScript name are told apart
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TypeNaming {
+    /// The first type keeps the name, the others are numbered: `User`, `User1`
+    #[default]
+    Numbered,
+    /// The types are prefixed with as much of their module path as it takes to tell them apart: `AdminUser`, `PublicUser`.
+    /// Exporting fails with a `NameCollision` when their module paths are the same
+    ModulePath,
+    /// Like `ModulePath`, and every derived type is written to a dto subfolder following its module path: `dto/admin/AdminUser.ts`
+    Folders,
+    /// Exporting fails with a `NameCollision`
+    Error,
+}
+
+/// Two Rust types that would be written with the same TypeScript name
+#[derive(Clone, Debug)]
+pub struct NameCollision {
+    pub name: String,
+    pub first: String,
+    pub second: String,
+}
+
+impl fmt::Display for NameCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` and `{}` are both exported as `{}`, rename one with `#[ts(rename = \"...\")]` or pick another `TypeNaming` or `Layout`", self.first, self.second, self.name)
+    }
+}
+
+impl Error for NameCollision {}
+
+/// The modules of a `module_path!()::Type` path, without the crate and the type
+fn module_segments(rust_path: &str) -> Vec<&str> {
+    let segments: Vec<&str> = rust_path.split("::").collect();
+    match segments.len() {
+        0..=2 => Vec::new(),
+        len => segments[1..len - 1].to_vec(),
+    }
+}
+
+/// The shortest module path endings, as PascalCase, that are different for every file.
+/// Fails with the indices of two files whose module paths can't tell them apart.
+fn distinct_module_prefixes(files: &[(u64, &str)]) -> Result<Vec<String>, (usize, usize)> {
+    let modules: Vec<Vec<&str>> = files.iter().map(|(_, path)| module_segments(path)).collect();
+    let deepest = modules.iter().map(Vec::len).max().unwrap_or(0);
+
+    let prefixes = |take: usize| -> Vec<String> {
+        modules.iter().map(|segments| {
+            segments[segments.len().saturating_sub(take)..].iter()
+                .map(|segment| segment.split('_').map(capitalize_first_letter).collect::<String>())
+                .collect()
+        }).collect()
+    };
+
+    if let Some(distinct) = (1..=deepest).map(prefixes).find(|p| p.iter().collect::<BTreeSet<_>>().len() == p.len()) {
+        return Ok(distinct)
+    }
+
+    let full = prefixes(deepest);
+    let clash = (0..full.len())
+        .flat_map(|i| (i + 1..full.len()).map(move |j| (i, j)))
+        .find(|(i, j)| full[*i] == full[*j])
+        .unwrap_or((0, 1));
+
+    Err(clash)
+}
+
+/// A type being hashed by `GlobalTypeRegistry`
+#[derive(Clone, Copy, Debug)]
+struct HashFrame {
+    type_id: TypeId,
+    /// The lowest stack position the hash refers back to
+    lowest: usize,
+    /// Some type further down refers back to this one
+    cyclic: bool,
+}
+
 #[derive(Clone, Debug)]
 pub enum HasIndexed {
     Prebuild(TypeId),
@@ -141,7 +374,7 @@ pub enum HasIndexed {
 #[derive(Default, Debug)]
 pub struct GlobalTypeRegistry {
     prebuild: Vec<TypeId>,
-    hasher_stopper: Vec<TypeId>,
+    hasher_stack: Vec<HashFrame>,
     hasher_hash: HashMap<TypeId, u64>,
     type_index: HashMap<TypeId, usize>,
     hash_index: HashMap<u64, usize>,
@@ -155,7 +388,8 @@ impl GlobalTypeRegistry {
 
     pub fn get_indexed(&self, index: &HasIndexed) -> &Component {
         let idx = match index {
-            HasIndexed::Prebuild(i) => self.type_index.get(i).expect(&format!("Builder error??? Index: {:?}\n\n{:?}",index, self)),
+            HasIndexed::Prebuild(i) => self.type_index.get(i)
+                .unwrap_or_else(|| panic!("{:?} was referenced while being defined but its definition never finished", i)),
             HasIndexed::Build(i) => i,
         };
 
@@ -169,7 +403,7 @@ impl GlobalTypeRegistry {
 
     pub fn reset(&mut self) {
         self.prebuild = Vec::new();
-        self.hasher_stopper = Vec::new();
+        self.hasher_stack = Vec::new();
         self.hasher_hash = HashMap::new();
         self.type_index = HashMap::new();
         self.hash_index = HashMap::new();
@@ -194,20 +428,47 @@ impl GlobalTypeRegistry {
     }
 
     pub fn start_hash(&mut self, type_id: TypeId) -> Option<u64> {
-        if !self.hasher_stopper.contains(&type_id) {
-            self.hasher_stopper.push(type_id);
-        } else {
-            if let Some(h) = self.hasher_hash.get(&type_id) {
-                return Some(*h);
-            } else {
-                return Some(0);
-            }
-        }  
+        if let Some(h) = self.hasher_hash.get(&type_id) {
+            return Some(*h);
+        }
+
+        let top = self.hasher_stack.len();
+
+        if let Some(position) = self.hasher_stack.iter().position(|frame| frame.type_id == type_id) {
+            // A cycle back to a type that is still being hashed. It is hashed by how far up the
+            // stack it is, so every type of a cycle gets the same hash whichever one is hashed first
+            self.hasher_stack[position].cyclic = true;
+            let lowest = &mut self.hasher_stack[top - 1].lowest;
+            *lowest = (*lowest).min(position);
+
+            let mut hasher = DefaultHasher::new();
+            "recursive".hash(&mut hasher);
+            (top - position).hash(&mut hasher);
+            return Some(hasher.finish());
+        }
+
+        self.hasher_stack.push(HashFrame { type_id, lowest: top, cyclic: false });
 
         return None
     }
 
     pub fn finalize_hash(&mut self, type_id: TypeId, hash: u64) {
+        if let Some(frame) = self.hasher_stack.last().filter(|frame| frame.type_id == type_id).copied() {
+            self.hasher_stack.pop();
+            let position = self.hasher_stack.len();
+
+            if frame.lowest < position {
+                let parent = &mut self.hasher_stack[position - 1].lowest;
+                *parent = (*parent).min(frame.lowest);
+            }
+
+            // Types of a cycle are hashed from where the cycle was entered, that hash
+            // only holds when hashing starts at the same type
+            if frame.lowest < position || frame.cyclic {
+                return;
+            }
+        }
+
         if !self.hasher_hash.contains_key(&type_id) {
             self.hasher_hash.insert(type_id, hash);
         }
@@ -236,8 +497,12 @@ impl GlobalTypeRegistry {
 pub struct ComponentFileBuilder {
     pub name: String,
     pub hash: u64,
+    /// The subfolder of the dto folder the file is written to, empty for the dto folder itself
+    pub folder: String,
+    /// The `module_path!()` and name of the derived type the file is written for
+    pub rust_path: Option<String>,
     pub imports: Vec<(String, Option<String>)>,
-    pub type_defs: HashSet<String>,
+    pub type_defs: BTreeSet<String>,
     pub content: String,
     pub exports: Vec<String>
 }
@@ -247,8 +512,10 @@ impl ComponentFileBuilder {
         Self {
             name,
             hash,
+            folder: String::new(),
+            rust_path: None,
             imports: Vec::new(),
-            type_defs: HashSet::new(),
+            type_defs: BTreeSet::new(),
             content: String::new(),
             exports: Vec::new(),
         }
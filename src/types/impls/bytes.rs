@@ -0,0 +1,10 @@
+//! serde_json writes bytes as an array of numbers
+
+use bytes::{Bytes, BytesMut};
+
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType};
+
+use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple};
+
+ts_simple!(Bytes, "Bytes", "Array<number>");
+ts_simple!(BytesMut, "BytesMut", "Array<number>");
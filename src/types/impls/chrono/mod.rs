@@ -1,4 +1,4 @@
-use chrono::{Utc, DateTime, Local, NaiveDate};
+use chrono::{Utc, DateTime, Local, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Duration};
 
 use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType, model::{ComponentReference, Component, Type, EnumRepresentation, InnerType}};
 
@@ -7,4 +7,10 @@ use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple, t
 
 ts_simple!(DateTime<Utc>, "DateTime<Utc>", "string");
 ts_simple!(DateTime<Local>, "DateTime<Local>", "string");
-ts_simple!(NaiveDate, "NaiveDate", "string");
\ No newline at end of file
+ts_simple!(DateTime<FixedOffset>, "DateTime<FixedOffset>", "string");
+ts_simple!(NaiveDate, "NaiveDate", "string");
+ts_simple!(NaiveDateTime, "NaiveDateTime", "string");
+ts_simple!(NaiveTime, "NaiveTime", "string");
+
+// Written as `(seconds, nanoseconds)`, chrono has a serde impl for it since 0.4.39
+ts_simple!(Duration, "Duration", "[number, number]");
@@ -0,0 +1,10 @@
+use std::{any::TypeId, collections::hash_map::DefaultHasher, hash::{Hash as _, Hasher as _}};
+
+use indexmap::{IndexMap, IndexSet};
+
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType, model::{ComponentReference, Component, Type}};
+
+use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_array, ts_map};
+
+ts_map!(IndexMap<K, V, S>, "IndexMap", S);
+ts_array!(IndexSet<T, S>, "IndexSet", "", hasher S);
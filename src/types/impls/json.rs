@@ -2,7 +2,7 @@ use std::any::TypeId;
 
 use serde_json::Value;
 
-use crate::types::{TypescriptType, builder::{GlobalTypeRegistry, HasIndexed}, model::{ComponentReference, Component, EnumRepresentation, Type, InnerType}};
+use crate::types::{TypescriptType, builder::{GlobalTypeRegistry, HasIndexed}, model::{Component, WideIntegers}};
 
 impl TypescriptType for Value {
     fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
@@ -26,7 +26,7 @@ impl TypescriptType for Value {
        String::from("any")
     }
 
-    fn ts_name() -> String {
+    fn ts_name(_integers: WideIntegers) -> String {
         String::from("any")
     }
 }
\ No newline at end of file
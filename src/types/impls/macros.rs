@@ -7,7 +7,7 @@ macro_rules! ts_simple {
             fn name() -> String {
                 String::from($typ_name)
             }
-            fn ts_name() -> String {
+            fn ts_name(_integers: crate::types::model::WideIntegers) -> String {
                 String::from($ts_typ)
             }
             fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
@@ -17,6 +17,26 @@ macro_rules! ts_simple {
     }
 }
 
+/// Integers JavaScript numbers can't hold exactly, written according to the `WideIntegers` policy of the export
+macro_rules! ts_wide_integer {
+    ($typ:ty,$typ_name:literal) => { 
+        impl TypescriptType for $typ {
+            fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+                boilerplate_simple_definition::<$typ>(crate::types::model::Type::WideInteger(String::from($typ_name)), registry)
+            }
+            fn name() -> String {
+                String::from($typ_name)
+            }
+            fn ts_name(integers: crate::types::model::WideIntegers) -> String {
+                String::from(integers.ts_type())
+            }
+            fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+                boilerplate_simple_hash::<$typ>(registry)
+            }
+        }
+    }
+}
+
 macro_rules! ts_tuple {
     ($($typ:ident),+) => { 
         impl<$($typ : TypescriptType + 'static),+> TypescriptType for ($($typ),+) {
@@ -31,10 +51,7 @@ macro_rules! ts_tuple {
                 let mut subs = Vec::new();
 
                 $(
-                    subs.push(ComponentReference {
-                        id: $typ::get_definition(registry),
-                        renamed: None,
-                    });
+                    subs.push(ComponentReference::new($typ::get_definition(registry)));
                 )+
             
                 let hash = Self::hash(registry); 
@@ -44,6 +61,12 @@ macro_rules! ts_tuple {
                     typ: Type::Struct(
                         InnerType::Tuple(subs)
                     ),
+                    input: None,
+                    generics: Vec::new(),
+                    docs: None,
+                    enum_style: crate::types::model::EnumStyle::Union,
+                    brand: false,
+                    rust_path: None,
                     hash
                 };
             
@@ -57,10 +80,10 @@ macro_rules! ts_tuple {
 
                 format!("({})", names.join(", "))
             }
-            fn ts_name() -> String {
+            fn ts_name(integers: crate::types::model::WideIntegers) -> String {
                 let mut names = Vec::new();
                 $(
-                    names.push($typ::ts_name());
+                    names.push($typ::ts_name(integers));
                 )+
                 format!("[{}]", names.join(", "))
             }
@@ -90,22 +113,19 @@ macro_rules! ts_tuple {
 }
 
 macro_rules! ts_array_base {
-    ($typ:ty, $ts_typ:expr, $typ_name:expr) => {
+    ($typ:ty, $typ_name:expr) => {
         fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
             let inner = T::get_definition(registry);
 
-            let comp = crate::types::model::ComponentReference {
-                id: inner,
-                renamed: None
-            };
+            let comp = crate::types::model::ComponentReference::new(inner);
         
             boilerplate_simple_definition::<$typ>(crate::types::model::Type::Array(comp), registry)
         }
         fn name() -> String {
             $typ_name
         }
-        fn ts_name() -> String {
-            String::from($ts_typ)
+        fn ts_name(integers: crate::types::model::WideIntegers) -> String {
+            format!("Array<{}>", T::ts_name(integers))
         }
         fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
             boilerplate_simple_hash::<$typ>(registry)
@@ -114,14 +134,122 @@ macro_rules! ts_array_base {
 }
 
 macro_rules! ts_array {
+    ($typ:ty, $typ_start:literal, $typ_end:literal, hasher $hasher:ident) => {
+        impl<T: TypescriptType + 'static, $hasher: 'static> TypescriptType for $typ {
+            crate::types::impls::ts_array_base![$typ, format!("{}{}", $typ_start, $typ_end)];
+        }
+    };
     ($typ:ty, $typ_start:literal, $typ_end:literal, $const:ident) => {
         impl<T: TypescriptType + 'static, const $const: usize> TypescriptType for $typ {
-            crate::types::impls::ts_array_base![$typ, format!("Array<{}>", T::name()), format!("{}{}; {}{}", $typ_start, T::name(), $const, $typ_end)];
+            crate::types::impls::ts_array_base![$typ, format!("{}{}; {}{}", $typ_start, T::name(), $const, $typ_end)];
         }
     };
     ($typ:ty, $typ_start:literal, $typ_end:literal) => { 
         impl<T: TypescriptType + 'static> TypescriptType for $typ {
-            crate::types::impls::ts_array_base![$typ, format!("Array<{}>",T::name()), format!("{}{}", $typ_start, $typ_end)];
+            crate::types::impls::ts_array_base![$typ, format!("{}{}", $typ_start, $typ_end)];
         }
     }
-}
\ No newline at end of file
+}
+
+macro_rules! ts_map_base {
+    ($map_name:literal) => {
+        fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+            let type_id = TypeId::of::<Self>();
+            if let Some(existing) = registry.return_existing(type_id) {
+                return existing
+            }
+
+            registry.start(type_id);
+
+            let key = ComponentReference::new(K::get_definition(registry));
+            let value = ComponentReference::new(V::get_definition(registry));
+
+            let hash = Self::hash(registry);
+
+            let component = Component {
+                name: Self::name(),
+                typ: Type::Record(key, value),
+                input: None,
+                generics: Vec::new(),
+                docs: None,
+                enum_style: crate::types::model::EnumStyle::Union,
+                brand: false,
+                rust_path: None,
+                hash
+            };
+
+            return registry.finalize(type_id, component)
+        }
+        fn name() -> String {
+            String::from($map_name)
+        }
+        fn ts_name(integers: crate::types::model::WideIntegers) -> String {
+            // Wide integer keys are strings whatever the policy, see `record_type`
+            format!("Record<{}, {}>", K::ts_name(crate::types::model::WideIntegers::String), V::ts_name(integers))
+        }
+        fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+            let type_id = ::std::any::TypeId::of::<Self>();
+
+            if let Some(h) = registry.start_hash(type_id) {
+                return h
+            }
+
+            let mut hasher = DefaultHasher::new();
+            "record".hash(&mut hasher);
+            K::hash(registry).hash(&mut hasher);
+            V::hash(registry).hash(&mut hasher);
+
+            let hash = hasher.finish();
+
+            registry.finalize_hash(type_id, hash);
+
+            return hash;
+        }
+    }
+}
+
+macro_rules! ts_map {
+    ($typ:ty, $typ_name:literal, $hasher:ident) => {
+        impl<K: TypescriptType + 'static, V: TypescriptType + 'static, $hasher: 'static> TypescriptType for $typ {
+            crate::types::impls::ts_map_base![$typ_name];
+        }
+    };
+    ($typ:ty, $typ_name:literal) => {
+        impl<K: TypescriptType + 'static, V: TypescriptType + 'static> TypescriptType for $typ {
+            crate::types::impls::ts_map_base![$typ_name];
+        }
+    }
+}
+macro_rules! ts_wrapper_base {
+    () => {
+        fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+            T::get_definition(registry)
+        }
+        fn ts_name(integers: crate::types::model::WideIntegers) -> String {
+            T::ts_name(integers)
+        }
+        fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+            T::hash(registry)
+        }
+    }
+}
+
+/// Smart pointers and other wrappers serde writes as the value they hold
+macro_rules! ts_wrapper {
+    ($typ:ty, $typ_name:literal, ?Sized) => {
+        impl<T: TypescriptType + ?Sized + 'static> TypescriptType for $typ {
+            crate::types::impls::ts_wrapper_base![];
+            fn name() -> String {
+                String::from($typ_name)
+            }
+        }
+    };
+    ($typ:ty, $typ_name:literal) => {
+        impl<T: TypescriptType + 'static> TypescriptType for $typ {
+            crate::types::impls::ts_wrapper_base![];
+            fn name() -> String {
+                String::from($typ_name)
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use ::std::{any::TypeId, collections::hash_map::DefaultHasher, hash::{ Hash as _, Hasher as _}};
 
-use super::{TypescriptType, builder::{GlobalTypeRegistry, HasIndexed}, model::{Component, Type}};
+use super::{TypescriptType, builder::{GlobalTypeRegistry, HasIndexed}, model::{Component, EnumStyle, Type}};
 
 pub mod std;
 pub mod chrono;
@@ -8,13 +8,37 @@ pub mod chrono;
 pub mod router;
 pub mod json;
 
+#[cfg(feature = "uuid")]
+pub mod uuid;
+#[cfg(feature = "rust_decimal")]
+pub mod rust_decimal;
+#[cfg(feature = "bigdecimal")]
+pub mod bigdecimal;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "url")]
+pub mod url;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+#[cfg(feature = "indexmap")]
+pub mod indexmap;
+#[cfg(feature = "smol_str")]
+pub mod smol_str;
+#[cfg(feature = "ordered-float")]
+pub mod ordered_float;
+
 #[macro_use]
 pub mod macros;
 
 pub(crate) use ts_simple;
+pub(crate) use ts_wide_integer;
 pub(crate) use ts_array;
 pub(crate) use ts_array_base;
 pub(crate) use ts_tuple;
+pub(crate) use ts_map;
+pub(crate) use ts_map_base;
+pub(crate) use ts_wrapper;
+pub(crate) use ts_wrapper_base;
 
 pub fn boilerplate_simple_definition<T: TypescriptType + 'static>(typ: Type, registry: &mut GlobalTypeRegistry) -> HasIndexed {
     let type_id = TypeId::of::<T>();
@@ -28,10 +52,31 @@ pub fn boilerplate_simple_definition<T: TypescriptType + 'static>(typ: Type, reg
     registry.finalize(type_id, Component {
         name,
         typ,
+        input: None,
+        generics: Vec::new(),
+        docs: None,
+        enum_style: EnumStyle::Union,
+        brand: false,
+        rust_path: None,
         hash,
     })
 }
 
+/// Registers the declaration shared by all instantiations of a generic type.
+/// `D` is a marker type that only exists once per declaration.
+pub fn boilerplate_declaration<D: 'static>(registry: &mut GlobalTypeRegistry, build: impl FnOnce(&mut GlobalTypeRegistry) -> Component) -> HasIndexed {
+    let type_id = TypeId::of::<D>();
+    if let Some(existing) = registry.return_existing(type_id) {
+        return existing
+    }
+
+    registry.start(type_id);
+
+    let component = build(registry);
+
+    registry.finalize(type_id, component)
+}
+
 pub fn boilerplate_simple_hash<T: TypescriptType + 'static>(registry: &mut GlobalTypeRegistry) -> u64 {
     let type_id = TypeId::of::<T>();
     if let Some(h) = registry.start_hash(type_id) {
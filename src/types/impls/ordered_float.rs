@@ -0,0 +1,8 @@
+use ordered_float::{NotNan, OrderedFloat};
+
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType};
+
+use super::ts_wrapper;
+
+ts_wrapper!(OrderedFloat<T>, "OrderedFloat");
+ts_wrapper!(NotNan<T>, "NotNan");
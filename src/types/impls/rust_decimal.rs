@@ -0,0 +1,9 @@
+//! `Decimal` is written as a string, unless `rust_decimal` is told otherwise with `serde-float`
+
+use rust_decimal::Decimal;
+
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType};
+
+use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple};
+
+ts_simple!(Decimal, "Decimal", "string");
@@ -1,9 +1,11 @@
 use core::hash;
-use std::{any::TypeId, collections::hash_map::DefaultHasher, hash::{Hash as _, Hasher as _}};
+use std::{any::TypeId, borrow::{Cow, ToOwned}, collections::{hash_map::DefaultHasher, HashMap, BTreeMap, HashSet, BTreeSet, VecDeque}, hash::{Hash as _, Hasher as _}};
+use std::{convert::Infallible, net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6}, path::PathBuf, rc::Rc, sync::Arc, time::{Duration, SystemTime}};
+use std::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize, NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize};
 
-use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType, model::{ComponentReference, Component, Type, EnumRepresentation, InnerType}};
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType, model::{ComponentReference, Component, Type, EnumRepresentation, EnumVariant, EnumStyle, InnerType, WideIntegers}};
 
-use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple, ts_array, ts_tuple};
+use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple, ts_wide_integer, ts_array, ts_tuple, ts_map, ts_wrapper};
 
 impl<T: TypescriptType + 'static, E: TypescriptType + 'static> TypescriptType for Result<T, E> {
     fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
@@ -14,15 +16,8 @@ impl<T: TypescriptType + 'static, E: TypescriptType + 'static> TypescriptType fo
 
         registry.start(type_id);
 
-        let ok = ComponentReference {
-            id: T::get_definition(registry),
-            renamed: None,
-        };
-        
-        let err = ComponentReference {
-            id: E::get_definition(registry),
-            renamed: None,
-        };
+        let ok = ComponentReference::new(T::get_definition(registry));
+        let err = ComponentReference::new(E::get_definition(registry));
 
         let hash = Self::hash(registry); 
 
@@ -30,8 +25,14 @@ impl<T: TypescriptType + 'static, E: TypescriptType + 'static> TypescriptType fo
             name: format!("Result"),
             typ: Type::Enum(
                 EnumRepresentation::Default, 
-                vec![(String::from("Ok"), InnerType::NewType(ok)), (String::from("Err"), InnerType::NewType(err))]
+                vec![EnumVariant::new("Ok", InnerType::NewType(ok)), EnumVariant::new("Err", InnerType::NewType(err))]
             ),
+            input: None,
+            generics: Vec::new(),
+            docs: None,
+            enum_style: EnumStyle::Union,
+            brand: false,
+            rust_path: None,
             hash
         };
 
@@ -64,7 +65,7 @@ impl<T: TypescriptType + 'static, E: TypescriptType + 'static> TypescriptType fo
         format!("Option<{},{}>", T::name(), E::name())
     }
 
-    fn ts_name() -> String {
+    fn ts_name(_integers: WideIntegers) -> String {
         String::from("Result")
     }
 }
@@ -78,19 +79,19 @@ impl<T: TypescriptType + 'static> TypescriptType for Option<T> {
 
         registry.start(type_id);
 
-        let some = ComponentReference {
-            id: T::get_definition(registry),
-            renamed: None,
-        };
+        let some = ComponentReference::new(T::get_definition(registry));
 
         let hash = Self::hash(registry); 
 
         let component = Component {
             name: format!("Option"),
-            typ: Type::Enum(
-                EnumRepresentation::Untagged, 
-                vec![(String::from("Ok"), InnerType::NewType(some)), (String::from("None"), InnerType::Null)]
-            ),
+            typ: Type::Nullable(some),
+            input: None,
+            generics: Vec::new(),
+            docs: None,
+            enum_style: EnumStyle::Union,
+            brand: false,
+            rust_path: None,
             hash
         };
 
@@ -105,9 +106,7 @@ impl<T: TypescriptType + 'static> TypescriptType for Option<T> {
         }
 
         let mut hasher = DefaultHasher::new();
-        "enum".hash(&mut hasher);
-        Self::name().hash(&mut hasher);
-        T::name().hash(&mut hasher);
+        "nullable".hash(&mut hasher);
         T::hash(registry).hash(&mut hasher);
 
         let hash = hasher.finish();
@@ -121,13 +120,114 @@ impl<T: TypescriptType + 'static> TypescriptType for Option<T> {
        format!("Option")
     }
 
-    fn ts_name() -> String {
-        T::name() + " | null"
+    fn ts_name(integers: WideIntegers) -> String {
+        T::ts_name(integers) + " | null"
+    }
+}
+
+/// Borrowed data is written like its owned form, `Cow<str>` is a `String`
+impl<'a, B: ToOwned + ?Sized + 'a> TypescriptType for Cow<'a, B> where B::Owned: TypescriptType + 'static {
+    fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+        B::Owned::get_definition(registry)
+    }
+    fn name() -> String {
+        String::from("Cow")
+    }
+    fn ts_name(integers: WideIntegers) -> String {
+        B::Owned::ts_name(integers)
+    }
+    fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+        B::Owned::hash(registry)
+    }
+}
+
+/// Unsized types only show up behind pointers (`Box<str>`, `Arc<[T]>`), they are written like their owned form
+impl TypescriptType for str {
+    fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+        String::get_definition(registry)
+    }
+    fn name() -> String {
+        String::from("str")
+    }
+    fn ts_name(integers: WideIntegers) -> String {
+        String::ts_name(integers)
+    }
+    fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+        <String as TypescriptType>::hash(registry)
+    }
+}
+
+impl<T: TypescriptType + 'static> TypescriptType for [T] {
+    fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+        Vec::<T>::get_definition(registry)
+    }
+    fn name() -> String {
+        String::from("[]")
+    }
+    fn ts_name(integers: WideIntegers) -> String {
+        Vec::<T>::ts_name(integers)
+    }
+    fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+        Vec::<T>::hash(registry)
+    }
+}
+
+/// `Duration` and `SystemTime` are written by serde as seconds and nanoseconds
+fn time_definition<T: TypescriptType + 'static>(secs: &str, nanos: &str, registry: &mut GlobalTypeRegistry) -> HasIndexed {
+    let fields = vec![
+        (String::from(secs), ComponentReference::new(u64::get_definition(registry))),
+        (String::from(nanos), ComponentReference::new(u32::get_definition(registry))),
+    ];
+
+    boilerplate_simple_definition::<T>(Type::Struct(InnerType::Object(fields)), registry)
+}
+
+impl TypescriptType for Duration {
+    fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+        time_definition::<Self>("secs", "nanos", registry)
+    }
+    fn name() -> String {
+        String::from("Duration")
+    }
+    fn ts_name(_integers: WideIntegers) -> String {
+        String::from("Duration")
+    }
+    fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+        boilerplate_simple_hash::<Self>(registry)
+    }
+}
+
+impl TypescriptType for SystemTime {
+    fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed {
+        time_definition::<Self>("secs_since_epoch", "nanos_since_epoch", registry)
+    }
+    fn name() -> String {
+        String::from("SystemTime")
+    }
+    fn ts_name(_integers: WideIntegers) -> String {
+        String::from("SystemTime")
+    }
+    fn hash(registry: &mut GlobalTypeRegistry) -> u64 {
+        boilerplate_simple_hash::<Self>(registry)
     }
 }
 
+ts_map!(HashMap<K, V, S>, "HashMap", S);
+ts_map!(BTreeMap<K, V>, "BTreeMap");
+
 ts_simple!(String, "String", "string");
 ts_simple!(&str, "&str", "string");
+ts_simple!(char, "char", "string");
+ts_simple!(PathBuf, "PathBuf", "string");
+
+ts_simple!(IpAddr, "IpAddr", "string");
+ts_simple!(Ipv4Addr, "Ipv4Addr", "string");
+ts_simple!(Ipv6Addr, "Ipv6Addr", "string");
+ts_simple!(SocketAddr, "SocketAddr", "string");
+ts_simple!(SocketAddrV4, "SocketAddrV4", "string");
+ts_simple!(SocketAddrV6, "SocketAddrV6", "string");
+
+ts_simple!(Infallible, "Infallible", "never");
 
 ts_simple!((), "()", "null");
 
@@ -136,12 +236,29 @@ ts_simple!(bool, "bool", "boolean");
 ts_simple!(u8, "u8", "number");
 ts_simple!(u16, "u16", "number");
 ts_simple!(u32, "u32", "number");
-ts_simple!(u64, "u64", "number");
+ts_wide_integer!(u64, "u64");
+ts_wide_integer!(u128, "u128");
+ts_wide_integer!(usize, "usize");
 
 ts_simple!(i8, "i8", "number");
 ts_simple!(i16, "i16", "number");
 ts_simple!(i32, "i32", "number");
-ts_simple!(i64, "i64", "number");
+ts_wide_integer!(i64, "i64");
+ts_wide_integer!(i128, "i128");
+ts_wide_integer!(isize, "isize");
+
+ts_simple!(NonZeroU8, "NonZeroU8", "number");
+ts_simple!(NonZeroU16, "NonZeroU16", "number");
+ts_simple!(NonZeroU32, "NonZeroU32", "number");
+ts_wide_integer!(NonZeroU64, "NonZeroU64");
+ts_wide_integer!(NonZeroU128, "NonZeroU128");
+ts_wide_integer!(NonZeroUsize, "NonZeroUsize");
+ts_simple!(NonZeroI8, "NonZeroI8", "number");
+ts_simple!(NonZeroI16, "NonZeroI16", "number");
+ts_simple!(NonZeroI32, "NonZeroI32", "number");
+ts_wide_integer!(NonZeroI64, "NonZeroI64");
+ts_wide_integer!(NonZeroI128, "NonZeroI128");
+ts_wide_integer!(NonZeroIsize, "NonZeroIsize");
 
 ts_simple!(f32, "f32", "number");
 ts_simple!(f64, "f64", "number");
@@ -149,6 +266,13 @@ ts_simple!(f64, "f64", "number");
 ts_array!(Vec<T>, "Vec", "");
 ts_array!([T; C], "[", "]", C);
 ts_array!(&[T], "[", "]");
+ts_array!(VecDeque<T>, "VecDeque", "");
+ts_array!(BTreeSet<T>, "BTreeSet", "");
+ts_array!(HashSet<T, S>, "HashSet", "", hasher S);
+
+ts_wrapper!(Box<T>, "Box", ?Sized);
+ts_wrapper!(Rc<T>, "Rc", ?Sized);
+ts_wrapper!(Arc<T>, "Arc", ?Sized);
 
 ts_tuple!(T1, T2);
 ts_tuple!(T1, T2, T3);
@@ -0,0 +1,38 @@
+//! The formats of `time`, turned on by the `time` feature. By default `time` writes
+//! tuples of numbers, the `time-human-readable` feature turns on its strings instead
+
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType};
+
+use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple};
+
+// (year, ordinal, hour, minute, second, nanosecond, offset hours, minutes, seconds)
+#[cfg(not(feature = "time-human-readable"))]
+ts_simple!(OffsetDateTime, "OffsetDateTime", "[number, number, number, number, number, number, number, number, number]");
+#[cfg(not(feature = "time-human-readable"))]
+ts_simple!(PrimitiveDateTime, "PrimitiveDateTime", "[number, number, number, number, number, number]");
+// (year, ordinal)
+#[cfg(not(feature = "time-human-readable"))]
+ts_simple!(Date, "Date", "[number, number]");
+// (hour, minute, second, nanosecond)
+#[cfg(not(feature = "time-human-readable"))]
+ts_simple!(Time, "Time", "[number, number, number, number]");
+#[cfg(not(feature = "time-human-readable"))]
+ts_simple!(UtcOffset, "UtcOffset", "[number, number, number]");
+// (seconds, nanoseconds)
+#[cfg(not(feature = "time-human-readable"))]
+ts_simple!(Duration, "Duration", "[number, number]");
+
+#[cfg(feature = "time-human-readable")]
+ts_simple!(OffsetDateTime, "OffsetDateTime", "string");
+#[cfg(feature = "time-human-readable")]
+ts_simple!(PrimitiveDateTime, "PrimitiveDateTime", "string");
+#[cfg(feature = "time-human-readable")]
+ts_simple!(Date, "Date", "string");
+#[cfg(feature = "time-human-readable")]
+ts_simple!(Time, "Time", "string");
+#[cfg(feature = "time-human-readable")]
+ts_simple!(UtcOffset, "UtcOffset", "string");
+#[cfg(feature = "time-human-readable")]
+ts_simple!(Duration, "Duration", "string");
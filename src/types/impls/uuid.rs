@@ -0,0 +1,7 @@
+use uuid::Uuid;
+
+use crate::types::{builder::{GlobalTypeRegistry, HasIndexed}, TypescriptType};
+
+use super::{boilerplate_simple_definition, boilerplate_simple_hash, ts_simple};
+
+ts_simple!(Uuid, "Uuid", "string");
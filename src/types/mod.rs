@@ -1,6 +1,6 @@
 use std::{any::TypeId, marker::PhantomData};
 
-use self::{model::{Component, AnyType}, builder::{GlobalTypeRegistry, HasIndexed}};
+use self::{model::{Component, AnyType, WideIntegers}, builder::{GlobalTypeRegistry, HasIndexed}};
 
 pub mod model;
 pub mod builder;
@@ -10,16 +10,47 @@ pub trait GetDefinition<T> {
     fn get_definition(self, registry: &mut GlobalTypeRegistry) -> HasIndexed;
     fn hash(self, registry: &mut GlobalTypeRegistry) -> u64;
     fn name(self) -> String;
-    fn ts_name(self) -> String;
+    fn ts_name(self, integers: WideIntegers) -> String;
 }
 
 pub trait TypescriptType {
     fn get_definition(registry: &mut GlobalTypeRegistry) -> HasIndexed;
     fn hash(registry: &mut GlobalTypeRegistry) -> u64;
     fn name() -> String;
-    fn ts_name() -> String;
+    /// How the type is written in TypeScript, wide integers according to `integers`
+    fn ts_name(integers: WideIntegers) -> String;
 }
 
+/// A type registered with `#[ts(export)]`, exported even when no route refers to it
+#[derive(Clone, Copy)]
+pub struct ExportedType {
+    pub register: fn(&mut GlobalTypeRegistry) -> HasIndexed,
+    /// The Rust path of the type, its module path followed by its name
+    pub path: fn() -> &'static str,
+}
+
+impl ExportedType {
+    pub const fn new<T: TypescriptType>() -> Self {
+        Self { register: T::get_definition, path: std::any::type_name::<T> }
+    }
+
+    /// Every type marked with `#[ts(export)]` in the binary, sorted by module path and name
+    pub fn all() -> Vec<ExportedType> {
+        let mut all: Vec<ExportedType> = inventory::iter::<ExportedType>.into_iter().copied().collect();
+        all.sort_by_key(|exported| (exported.path)());
+
+        all
+    }
+}
+
+impl std::fmt::Debug for ExportedType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExportedType").field("path", &(self.path)()).finish_non_exhaustive()
+    }
+}
+
+inventory::collect!(ExportedType);
+
 impl<T> GetDefinition<T> for &TypeHolder<T> {
     fn get_definition(self, registry: &mut GlobalTypeRegistry) -> HasIndexed {
         let id = TypeId::of::<AnyType>();
@@ -35,7 +66,7 @@ impl<T> GetDefinition<T> for &TypeHolder<T> {
     fn name(self) -> String {
         String::from("any")
     }
-    fn ts_name(self) -> String {
+    fn ts_name(self, _integers: WideIntegers) -> String {
         String::from("any")
     }
     fn hash(self, registry: &mut GlobalTypeRegistry) -> u64 {
@@ -53,8 +84,8 @@ impl<T: TypescriptType> GetDefinition<T> for &TypeHolderStrict<T> {
     fn hash(self, registry: &mut GlobalTypeRegistry) -> u64 {
         T::hash(registry)
     }
-    fn ts_name(self) -> String {
-        T::ts_name()
+    fn ts_name(self, integers: WideIntegers) -> String {
+        T::ts_name(integers)
     }
 }
 impl<T: TypescriptType> GetDefinition<T> for &mut &TypeHolder<T> {
@@ -67,8 +98,8 @@ impl<T: TypescriptType> GetDefinition<T> for &mut &TypeHolder<T> {
     fn hash(self, registry: &mut GlobalTypeRegistry) -> u64 {
         T::hash(registry)
     }
-    fn ts_name(self) -> String {
-        T::ts_name()
+    fn ts_name(self, integers: WideIntegers) -> String {
+        T::ts_name(integers)
     }
 }
 
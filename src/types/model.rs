@@ -1,6 +1,6 @@
-use std::{collections::hash_map::DefaultHasher, hash::{Hasher as _, Hash as _}, collections::{HashMap, HashSet}};
+use std::{collections::hash_map::DefaultHasher, hash::{Hasher as _, Hash as _}, collections::{BTreeMap, HashSet}};
 
-use crate::Postion;
+use crate::{Postion, utils::{is_ts_identifier, ts_doc_comment, ts_property_key, ts_string_literal, ts_type_ident}};
 
 use super::builder::{HasIndexed, TypeBuilder, GlobalTypeRegistry};
 
@@ -16,7 +16,20 @@ pub struct AnyType;
 #[derive(Clone, Debug)]
 pub struct Component {
     pub name: String,
+    /// The shape the server sends
     pub typ: Type,
+    /// The shape the server accepts, when it differs from `typ`
+    pub input: Option<Type>,
+    /// The type parameters of a generic declaration
+    pub generics: Vec<String>,
+    /// The Rust doc comment, written as JSDoc
+    pub docs: Option<String>,
+    /// How a fieldless enum is written
+    pub enum_style: EnumStyle,
+    /// A newtype written as a branded alias with a constructor, `#[ts(brand)]`
+    pub brand: bool,
+    /// `module_path!()` and the name of derived types, used to tell types with the same name apart
+    pub rust_path: Option<String>,
     pub hash: u64,
 }
 
@@ -28,83 +41,245 @@ impl Component {
         Self {
             name: String::from("any"),
             typ: Type::Any,
+            input: None,
+            generics: Vec::new(),
+            docs: None,
+            enum_style: EnumStyle::Union,
+            brand: false,
+            rust_path: None,
             hash: hasher.finish(),
         }
     }
 
+    /// The shape of this component at the given position
+    pub fn shape(&self, pos: Postion) -> &Type {
+        match pos {
+            Postion::Body => self.input.as_ref().unwrap_or(&self.typ),
+            Postion::Result => &self.typ,
+        }
+    }
+
+    /// Whether this component, or anything it references, is written differently
+    /// than it is read
+    pub fn differs_by_direction(&self, registry: &GlobalTypeRegistry) -> bool {
+        self.differs_by_direction_inner(registry, &mut HashSet::new())
+    }
+
+    fn differs_by_direction_inner(&self, registry: &GlobalTypeRegistry, visited: &mut HashSet<u64>) -> bool {
+        if self.input.is_some() {
+            return true
+        }
+
+        if !visited.insert(self.hash) {
+            return false
+        }
+
+        self.typ.references().into_iter()
+            .any(|r| registry.get_indexed(&r.id).differs_by_direction_inner(registry, visited))
+    }
+
+    /// Whether this component refers to a generic declaration or one of its parameters
+    /// and therefore has to be written out where it is used
+    pub fn uses_generics(&self, registry: &GlobalTypeRegistry, pos: Postion) -> bool {
+        match self.shape(pos) {
+            Type::Generic(_, _) |
+            Type::Param(_) => true,
+            Type::Array(x) |
+            Type::Nullable(x) => registry.get_indexed(&x.id).uses_generics(registry, pos),
+            Type::Record(k, v) => registry.get_indexed(&k.id).uses_generics(registry, pos) || registry.get_indexed(&v.id).uses_generics(registry, pos),
+            _ => false
+        }
+    }
+
+    /// The file name and hash this component is emitted under at the given position.
+    /// Input shapes get their own `...Input` type when the directions differ.
+    pub fn file_for(&self, registry: &GlobalTypeRegistry, pos: Postion) -> (String, u64) {
+        match pos {
+            Postion::Body if self.differs_by_direction(registry) => {
+                let mut hasher = DefaultHasher::new();
+                self.hash.hash(&mut hasher);
+                "input".hash(&mut hasher);
+
+                (format!("{}Input", self.name), hasher.finish())
+            }
+            _ => (self.name.clone(), self.hash)
+        }
+    }
+
     pub fn get_import_component(&self, registry: &GlobalTypeRegistry, pos: Postion) -> Option<Vec<Component>> {
         match &self.typ {
-            Type::Array(x) => registry.get_indexed(&x.id).get_import_component(registry, pos),
+            Type::Array(x) |
+            Type::Nullable(x) => registry.get_indexed(&x.id).get_import_component(registry, pos),
+            Type::Record(_, x) => registry.get_indexed(&x.id).get_import_component(registry, pos),
             Type::Struct(_) => Some(vec![self.clone()]),
-            Type::Enum(_, vals) => {
-                if let Postion::Body = pos {
-                    return Some(vec![self.clone()])
+            Type::Enum(_, _) |
+            Type::Generic(_, _) => {
+                if let (Postion::Result, Some((ok, err))) = (pos, self.result_parts(registry)) {
+                    return match (registry.get_indexed(&ok.id).get_import_component(registry, pos), registry.get_indexed(&err.id).get_import_component(registry, pos)) {
+                        (None, None) => None,
+                        (None, Some(x)) => Some(x),
+                        (Some(x), None) => Some(x),
+                        (Some(mut x), Some(y)) => {
+                            x.extend(y);
+                            Some(x)
+                        },
+                    }
                 }
 
-                if vals.len() == 2 && vals[0].0 == "Ok" && vals[1].0 == "Err" {
-                    if let (InnerType::NewType(ok), InnerType::NewType(err)) = (&vals[0].1, &vals[1].1) {
-                        return match (registry.get_indexed(&ok.id).get_import_component(registry, pos), registry.get_indexed(&err.id).get_import_component(registry, pos)) {
-                            (None, None) => None,
-                            (None, Some(x)) => Some(x),
-                            (Some(x), None) => Some(x),
-                            (Some(mut x), Some(y)) => {
-                                x.extend(y);
-                                Some(x)
-                            },
+                let args = match &self.typ {
+                    Type::Generic(decl, args) => {
+                        let mut comps = vec![registry.get_indexed(&decl.id).clone()];
+                        for arg in args {
+                            comps.extend(registry.get_indexed(&arg.id).get_import_component(registry, pos).unwrap_or_default());
                         }
-                    }
-                }
-                Some(vec![self.clone()])
+                        comps
+                    },
+                    _ => vec![self.clone()]
+                };
+
+                Some(args)
             },
+            Type::Param(_) => None,
             Type::SimpleType(_) => None,
+            Type::WideInteger(_) => None,
             Type::Any => None,
             Type::None => None,
         }
     }
 
-    pub fn get_client_result(&self, registry: &GlobalTypeRegistry) -> String {
+    /// The `Ok` and `Err` values if this component is a `Result`
+    fn result_parts<'a>(&'a self, registry: &'a GlobalTypeRegistry) -> Option<(&'a ComponentReference, &'a ComponentReference)> {
         match &self.typ {
-            Type::Enum(_, vals) => {
-                if vals.len() == 2 && vals[0].0 == "Ok" && vals[1].0 == "Err" {
-                    if let (InnerType::NewType(ok), InnerType::NewType(err)) = (&vals[0].1, &vals[1].1) {
-                        let ok = ok.renamed.as_ref().map(Clone::clone).unwrap_or(registry.get_indexed(&ok.id).get_ts_name(registry));
-                        let err = err.renamed.as_ref().map(Clone::clone).unwrap_or(registry.get_indexed(&err.id).get_ts_name(registry));
-                        format!("ApiResult<{}, {}>", ok, err)
-                    } else {
-                        format!("ApiResult<{}, any>", self.get_ts_name(registry))
-                    }
-                    
+            Type::Enum(_, vals) if vals.len() == 2 && vals[0].name == "Ok" && vals[1].name == "Err" => {
+                match (&vals[0].inner, &vals[1].inner) {
+                    (InnerType::NewType(ok), InnerType::NewType(err)) => Some((ok, err)),
+                    _ => None
+                }
+            },
+            Type::Generic(decl, args) if args.len() == 2 => {
+                let decl = registry.get_indexed(&decl.id);
+                if decl.name == "Result" && decl.result_parts(registry).is_some() {
+                    Some((&args[0], &args[1]))
                 } else {
-                    format!("ApiResult<{}, any>", self.get_ts_name(registry))
+                    None
                 }
             },
-            _=> format!("ApiResult<{}, any>", self.get_ts_name(registry))
+            _ => None
         }
     }
 
-    pub fn get_ts_name(&self, registry: &GlobalTypeRegistry) -> String {
-        match &self.typ {
-            Type::Array(x) => format!("Array<{}>", registry.get_indexed(&x.id).get_ts_name(registry)),
-            Type::Struct(_) => self.name.clone(),
-            Type::Enum(_, _) => self.name.clone(),
+    /// The result type of a route function in `module`, see `client_type`
+    pub fn get_client_result(&self, registry: &GlobalTypeRegistry, builder: &TypeBuilder, module: &str, imports: &mut BTreeMap<String, String>) -> String {
+        match self.result_parts(registry) {
+            Some((ok, err)) => {
+                let ok = ok.renamed.clone().unwrap_or_else(|| registry.get_indexed(&ok.id).client_type(registry, builder, module, imports, Postion::Result));
+                let err = err.renamed.clone().unwrap_or_else(|| registry.get_indexed(&err.id).client_type(registry, builder, module, imports, Postion::Result));
+                format!("ApiResult<{}, {}>", ok, err)
+            },
+            None => format!("ApiResult<{}, any>", self.client_type(registry, builder, module, imports, Postion::Result))
+        }
+    }
+
+    /// The type of a route input or result in `module`. Named types, generic arguments included, are
+    /// written under the name they are exported as and their imports are added to `imports`
+    pub fn client_type(&self, registry: &GlobalTypeRegistry, builder: &TypeBuilder, module: &str, imports: &mut BTreeMap<String, String>, pos: Postion) -> String {
+        let client_type = |r: &ComponentReference, imports: &mut BTreeMap<String, String>| {
+            registry.get_indexed(&r.id).client_type(registry, builder, module, imports, pos)
+        };
+
+        match self.shape(pos) {
+            Type::Array(x) => format!("Array<{}>", client_type(x, imports)),
+            Type::Nullable(x) => format!("{} | null", client_type(x, imports)),
+            Type::Record(k, v) => {
+                let key = client_type(k, imports);
+                record_type(registry.get_indexed(&k.id), key, client_type(v, imports), pos)
+            },
+            Type::Struct(_) |
+            Type::Enum(_, _) => {
+                let (name, hash) = self.file_for(registry, pos);
+                let (typ, import) = builder.get_type_and_import(&name, hash, module);
+                if !import.is_empty() {
+                    imports.insert(typ.clone(), import);
+                }
+                typ
+            },
+            Type::Generic(decl, args) => {
+                let decl = client_type(decl, imports);
+                let args = args.iter().map(|a| client_type(a, imports)).collect::<Vec<_>>();
+                format!("{}<{}>", decl, args.join(", "))
+            },
+            _ => self.get_ts_name(registry, builder.options.wide_integers, pos)
+        }
+    }
+
+    pub fn get_ts_name(&self, registry: &GlobalTypeRegistry, integers: WideIntegers, pos: Postion) -> String {
+        match self.shape(pos) {
+            Type::Array(x) => format!("Array<{}>", registry.get_indexed(&x.id).get_ts_name(registry, integers, pos)),
+            Type::Nullable(x) => format!("{} | null", registry.get_indexed(&x.id).get_ts_name(registry, integers, pos)),
+            Type::Record(k, v) => {
+                let key = registry.get_indexed(&k.id);
+                record_type(key, key.get_ts_name(registry, integers, pos), registry.get_indexed(&v.id).get_ts_name(registry, integers, pos), pos)
+            },
+            Type::Struct(_) |
+            Type::Enum(_, _) => self.file_for(registry, pos).0,
+            Type::Generic(decl, args) => {
+                let args = args.iter().map(|a| registry.get_indexed(&a.id).get_ts_name(registry, integers, pos)).collect::<Vec<_>>();
+                format!("{}<{}>", registry.get_indexed(&decl.id).get_ts_name(registry, integers, pos), args.join(", "))
+            },
+            Type::Param(x) => x.clone(),
             Type::SimpleType(x) => x.clone(),
+            Type::WideInteger(_) => String::from(integers.ts_type()),
             Type::Any => String::from("any"),
             Type::None => String::from("null"),
         }
     }
 
-    pub fn build(&self, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry) -> Option<String> {
+    pub fn build(&self, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, pos: Postion) -> Option<String> {
         if self.name == "Result" {
             println!("Result hash: {}!!!!", self.hash)
         }
-        self.typ.build(&self.name, self.hash, builder, registry)
+        let (name, hash) = self.file_for(registry, pos);
+        self.shape(pos).build(&name, self, hash, builder, registry, pos)
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct ComponentReference {
     pub id: HasIndexed,
-    pub renamed: Option<String>
+    pub renamed: Option<String>,
+    /// The value may be missing on the wire (`skip_serializing_if`, `default`, ...)
+    pub optional: bool,
+    /// The value never shows up on the wire (`#[serde(skip)]`)
+    pub skip: bool,
+    /// The fields of the value are inlined into the parent (`#[serde(flatten)]`)
+    pub flatten: bool,
+    /// Written instead of the referenced type (`#[ts(type = "...")]`)
+    pub ts_type: Option<String>,
+    /// The property name on the TypeScript side, the wire name stays untouched (`#[ts(rename = "...")]`)
+    pub ts_name: Option<String>,
+    /// The value may be `null` (`#[ts(nullable)]`)
+    pub nullable: bool,
+    /// The referenced type is written out in place instead of being imported (`#[ts(inline)]`)
+    pub inline: bool,
+    /// The doc comment of the field
+    pub docs: Option<String>,
+}
+
+impl ComponentReference {
+    pub fn new(id: HasIndexed) -> Self {
+        Self {
+            id,
+            renamed: None,
+            optional: false,
+            skip: false,
+            flatten: false,
+            ts_type: None,
+            ts_name: None,
+            nullable: false,
+            inline: false,
+            docs: None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -116,19 +291,36 @@ pub enum InnerType {
     Null
 }
 
-fn update_declarations(declarations: &mut HashMap<String, String>, subcomponent: &Component, renamed: &Option<String>, registry: &GlobalTypeRegistry) {
+/// A map does not have to contain every variant of an enum key. JSON object keys are strings,
+/// wide integer keys are written as `string` whatever the `WideIntegers` policy
+fn record_type(key: &Component, key_type: String, value_type: String, pos: Postion) -> String {
+    match key.shape(pos) {
+        Type::WideInteger(_) => format!("Record<string, {}>", value_type),
+        Type::Enum(repr, variants) if Type::unit_variants(repr, variants, &key.generics).is_some() => {
+            format!("Partial<Record<{}, {}>>", key_type, value_type)
+        },
+        _ => format!("Record<{}, {}>", key_type, value_type)
+    }
+}
+
+fn update_declarations(declarations: &mut BTreeMap<String, String>, subcomponent: &Component, renamed: &Option<String>, registry: &GlobalTypeRegistry, integers: WideIntegers, pos: Postion) {
     if renamed.is_some() {
         let new_name = renamed.as_ref().unwrap();
         if declarations.contains_key(new_name) {
             return
         };
 
-        match subcomponent.typ {
+        match subcomponent.shape(pos) {
             Type::Array(_) |     
+            Type::Record(_, _) |
+            Type::Nullable(_) |
+            Type::Generic(_, _) |
+            Type::Param(_) |
             Type::SimpleType(_) |
+            Type::WideInteger(_) |
             Type::Any |
             Type::None => {
-                declarations.insert(new_name.to_owned(), format!("type {} = {};", new_name, subcomponent.get_ts_name(registry)));
+                declarations.insert(new_name.to_owned(), format!("type {} = {};", new_name, subcomponent.get_ts_name(registry, integers, pos)));
             },
 
             Type::Struct(_) |
@@ -139,75 +331,249 @@ fn update_declarations(declarations: &mut HashMap<String, String>, subcomponent:
 
 impl InnerType {
 
+    /// Every component this type refers to directly
+    pub fn references(&self) -> Vec<&ComponentReference> {
+        match self {
+            InnerType::Object(fields) => fields.iter().map(|(_, r)| r).collect(),
+            InnerType::Tuple(refs) => refs.iter().collect(),
+            InnerType::NewType(r) => vec![r],
+            InnerType::SimpleVariant(_) |
+            InnerType::Null => Vec::new(),
+        }
+    }
+
     pub fn inner_query_string_builder(&self, name: &str, registry: &GlobalTypeRegistry) -> String {
         match self {
             InnerType::Object(x) => {
                 let mut assignments = Vec::new();
-                for (field, r) in x.iter() {
+                for (field, r) in x.iter().filter(|(_, r)| !r.skip) {
                     let sub = registry.get_indexed(&r.id);
-                    assignments.push(sub.typ.build_inner_query_string(name, &format!(".{}", field)));
+                    if r.flatten {
+                        assignments.push(sub.shape(Postion::Body).build_query_string(name, registry));
+                        continue;
+                    }
+
+                    let property = r.ts_name.as_ref().unwrap_or(field);
+                    let accessor = match ts_property_key(property) {
+                        key if key == *property => format!(".{}", key),
+                        key => format!("[{}]", key),
+                    };
+                    assignments.push(sub.shape(Postion::Body).build_inner_query_string(name, &accessor, field));
                 }
                 return assignments.join("\n");
             },
             InnerType::Tuple(x) => {
                 let mut assignments = Vec::new();
-                for (field, r) in x.iter().enumerate() {
+                for (field, r) in x.iter().filter(|r| !r.skip).enumerate() {
                     let sub = registry.get_indexed(&r.id);
-                    assignments.push(sub.typ.build_inner_query_string(name, &format!("[{}]", field)));
+                    assignments.push(sub.shape(Postion::Body).build_inner_query_string(name, &format!("[{}]", field), &field.to_string()));
                 }
                 return assignments.join("\n");
             },
             InnerType::NewType(x) => {
                 let sub = registry.get_indexed(&x.id);
-                sub.typ.build_query_string(name, registry)
+                sub.shape(Postion::Body).build_query_string(name, registry)
             },
             _ => return String::new()
         }
     }
 
-    pub fn build(&self, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut HashMap<String, String>, repr: Option<(EnumRepresentation, &str)>) -> (String, HashMap<String,(String, Option<String>)>) {
+    /// Builds the referenced component and returns the type it is written as in the current file,
+    /// `#[ts(...)]` overrides included
+    fn reference_type(refr: &ComponentReference, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> String {
+        let typ = match &refr.ts_type {
+            Some(ts_type) => ts_type.clone(),
+            None if refr.inline => Self::inline_type(refr, builder, registry, declarations, imports, pos),
+            None => Self::named_type(refr, builder, registry, declarations, imports, pos),
+        };
+
+        if refr.nullable && !typ.ends_with(" | null") {
+            format!("{} | null", typ)
+        } else {
+            typ
+        }
+    }
+
+    /// Writes the shape of the referenced type in place, only the outermost named type is inlined.
+    /// Generic types are written with their parameters replaced by the arguments.
+    fn inline_type(refr: &ComponentReference, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> String {
+        let sub_comp = registry.get_indexed(&refr.id);
+
+        match sub_comp.shape(pos) {
+            Type::Generic(decl, args) => {
+                let args = args.iter()
+                    .map(|arg| Self::reference_type(arg, builder, registry, declarations, imports, pos))
+                    .collect::<Vec<_>>();
+                let bindings = registry.get_indexed(&decl.id).generics.iter().cloned().zip(args).collect();
+
+                let outer = std::mem::replace(&mut builder.type_args, bindings);
+                let inlined = Self::inline_type(decl, builder, registry, declarations, imports, pos);
+                builder.type_args = outer;
+
+                inlined
+            },
+            Type::Struct(inner) => {
+                let (content, sub_imports) = inner.build(builder, registry, declarations, None, pos);
+                imports.extend(sub_imports);
+                content.replace('\n', "\n\t")
+            },
+            Type::Enum(repr, variants) => {
+                let variants = Type::build_variants(repr, variants, builder, registry, declarations, imports, pos).into_iter()
+                    .map(|(_, content)| content.replace('\n', "\n\t"))
+                    .collect::<Vec<_>>();
+                format!("({})", variants.join(" | "))
+            },
+            Type::Array(x) => format!("Array<{}>", Self::inline_type(x, builder, registry, declarations, imports, pos)),
+            Type::Nullable(x) => format!("{} | null", Self::inline_type(x, builder, registry, declarations, imports, pos)),
+            Type::Record(k, v) => {
+                let key = Self::reference_type(k, builder, registry, declarations, imports, pos);
+                record_type(registry.get_indexed(&k.id), key, Self::inline_type(v, builder, registry, declarations, imports, pos), pos)
+            },
+            _ => Self::named_type(refr, builder, registry, declarations, imports, pos)
+        }
+    }
+
+    /// Builds the referenced component and returns the name it can be used by in the current file
+    fn named_type(refr: &ComponentReference, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> String {
+        let sub_comp = registry.get_indexed(&refr.id);
+
+        if sub_comp.uses_generics(registry, pos) {
+            let inline = Self::generic_reference(sub_comp, builder, registry, declarations, imports, pos);
+
+            return match &refr.renamed {
+                Some(alias) => {
+                    declarations.entry(alias.clone()).or_insert_with(|| format!("type {} = {};", alias, inline));
+                    alias.clone()
+                },
+                None => inline
+            }
+        }
+
+        // Records and nullable values are written in place, the types they refer to are imported
+        let in_place = match sub_comp.shape(pos) {
+            Type::Record(k, v) => Some(Self::record_reference(k, v, builder, registry, declarations, imports, pos)),
+            Type::Nullable(x) => Some(format!("{} | null", Self::reference_type(x, builder, registry, declarations, imports, pos))),
+            _ => None
+        };
+
+        if let Some(typ) = in_place {
+            return match &refr.renamed {
+                Some(alias) => {
+                    declarations.entry(alias.clone()).or_insert_with(|| format!("type {} = {};", alias, typ));
+                    alias.clone()
+                },
+                None => typ
+            }
+        }
+
+        let renamed_comp = if let Some(import) = sub_comp.build(builder, registry, pos) {
+            let (name,_) = builder.get_type_and_import(&import, sub_comp.file_for(registry, pos).1, "");
+            imports.insert(name.clone(), (name.clone(), refr.renamed.clone()));
+            refr.renamed.clone().or_else(|| {
+                match sub_comp.shape(pos) {
+                    Type::Array(_) => Some(format!("{}Array", name)),
+                    Type::Record(_, _) => Some(format!("{}Record", name)),
+                    _ => Some(name)
+                } 
+            })
+        } else {
+            refr.renamed.clone()
+        };
+
+        update_declarations(declarations, sub_comp, &renamed_comp, registry, builder.options.wide_integers, pos);
+
+        renamed_comp.unwrap_or_else(|| sub_comp.get_ts_name(registry, builder.options.wide_integers, pos))
+    }
+
+    fn record_reference(key: &ComponentReference, value: &ComponentReference, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> String {
+        let key_type = Self::reference_type(key, builder, registry, declarations, imports, pos);
+        let value_type = Self::reference_type(value, builder, registry, declarations, imports, pos);
+
+        record_type(registry.get_indexed(&key.id), key_type, value_type, pos)
+    }
+
+    /// Generic instances are written out at the use site, e.g. `Page<User>`
+    fn generic_reference(comp: &Component, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> String {
+        match comp.shape(pos) {
+            Type::Param(name) => builder.type_args.get(name).cloned().unwrap_or_else(|| name.clone()),
+            Type::Generic(decl, args) => {
+                let decl = Self::reference_type(decl, builder, registry, declarations, imports, pos);
+                let args = args.iter()
+                    .map(|arg| Self::reference_type(arg, builder, registry, declarations, imports, pos))
+                    .collect::<Vec<_>>();
+
+                format!("{}<{}>", decl, args.join(", "))
+            },
+            Type::Array(x) => format!("Array<{}>", Self::reference_type(x, builder, registry, declarations, imports, pos)),
+            Type::Record(k, v) => Self::record_reference(k, v, builder, registry, declarations, imports, pos),
+            Type::Nullable(x) => format!("{} | null", Self::reference_type(x, builder, registry, declarations, imports, pos)),
+            _ => comp.get_ts_name(registry, builder.options.wide_integers, pos)
+        }
+    }
+
+    /// `#[serde(flatten)]` merges the fields of the referenced type into the parent object,
+    /// maps catch all remaining keys. A flattened `None` adds no fields, the fields of an `Option` are optional
+    fn flattened_type(refr: &ComponentReference, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> String {
+        match registry.get_indexed(&refr.id).shape(pos) {
+            Type::Record(_, value) => {
+                format!("{{ [key: string]: {} }}", Self::reference_type(value, builder, registry, declarations, imports, pos))
+            },
+            Type::Nullable(inner) => {
+                format!("Partial<{}>", Self::flattened_type(inner, builder, registry, declarations, imports, pos))
+            },
+            _ => Self::reference_type(refr, builder, registry, declarations, imports, pos)
+        }
+    }
+
+    pub fn build(&self, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, repr: Option<(EnumRepresentation, &str)>, pos: Postion) -> (String, BTreeMap<String,(String, Option<String>)>) {
         let (content, imports) = match self {
             InnerType::Object(fields) => {
                 let mut result = String::from("{");
-                let mut imports = HashMap::new();
+                let mut imports = BTreeMap::new();
 
                 if let Some((EnumRepresentation::Internally(tag), typ)) = &repr {
-                    result += &format!("\n\t{}: {}", tag, typ);
+                    result += &format!("\n\t{}: {}", ts_property_key(tag), ts_string_literal(typ));
                 }
 
-                for (field, refr) in fields {
-                    let sub_comp = registry.get_indexed(&refr.id);
-
-                    let renamed_comp = if let Some(import) = sub_comp.build(builder, registry) {
-                        let (name,_) = builder.get_type_and_import(&import, sub_comp.hash, 0);
-                        imports.insert(name.clone(), (name.clone(), refr.renamed.clone()));
-                        refr.renamed.clone().or_else(|| {
-                            match sub_comp.typ {
-                                Type::Array(_) => Some(format!("{}Array", name)),
-                                _ => Some(name)
-                            } 
-                        })
-                    } else {
-                        refr.renamed.clone()
-                    };
+                let mut flattened = Vec::new();
+
+                for (field, refr) in fields.iter().filter(|(_, r)| !r.skip) {
+                    if refr.flatten {
+                        flattened.push(Self::flattened_type(refr, builder, registry, declarations, &mut imports, pos));
+                        continue;
+                    }
 
-                    update_declarations(declarations, sub_comp, &renamed_comp, registry);
+                    let field_type = Self::reference_type(refr, builder, registry, declarations, &mut imports, pos);
 
                     if !result.ends_with('{') {
                         result += &format!(";");
                     }
 
-                    result += &format!("\n\t{}: {}", field, renamed_comp.as_ref().unwrap_or(&sub_comp.get_ts_name(registry)));
+                    let optional = if refr.optional { "?" } else { "" };
+
+                    let field = refr.ts_name.as_ref().unwrap_or(field);
+
+                    let docs = refr.docs.as_deref().map(|docs| ts_doc_comment(docs, "\t")).unwrap_or_default();
+
+                    result += &format!("\n{}\t{}{}: {}", docs, ts_property_key(field), optional, field_type);
                 }
 
                 result += "\n}";
 
+                if !flattened.is_empty() {
+                    result = if result == "{\n}" {
+                        flattened.join(" & ")
+                    } else {
+                        format!("{} & {}", result, flattened.join(" & "))
+                    };
+                }
+
                 let result = match repr {
                     Some((EnumRepresentation::Adjacently(tag, var), typ)) => {
-                        format!("{{\n\t{}: {};\n\t{}: {}\n}}", tag, typ, var, result)
+                        format!("{{\n\t{}: {};\n\t{}: {}\n}}", ts_property_key(&tag), ts_string_literal(typ), ts_property_key(&var), result)
                     }
                     Some((EnumRepresentation::Default, typ)) => {
-                        format!("{{\n\t{}: {}\n}}", typ, result)
+                        format!("{{\n\t{}: {}\n}}", ts_property_key(typ), result)
                     }
                     _ => result
                 };
@@ -216,40 +582,25 @@ impl InnerType {
             }
             InnerType::Tuple(refs) => {
                 let mut result = String::from("[");
-                let mut imports = HashMap::new();
-
-                for refr in refs {
-                    let sub_comp = registry.get_indexed(&refr.id);
-
-                    let renamed_comp = if let Some(import) = sub_comp.build(builder, registry) {
-                        let (name,_) = builder.get_type_and_import(&import, sub_comp.hash, 0);
-                        imports.insert(name.clone(), (name.clone(), refr.renamed.clone()));
-                        refr.renamed.clone().or_else(|| {
-                            match sub_comp.typ {
-                                Type::Array(_) => Some(format!("{}Array", name)),
-                                _ => Some(name)
-                            } 
-                        })
-                    } else {
-                        refr.renamed.clone()
-                    };
+                let mut imports = BTreeMap::new();
 
-                    update_declarations(declarations, sub_comp, &refr.renamed, registry);
+                for refr in refs.iter().filter(|r| !r.skip) {
+                    let field_type = Self::reference_type(refr, builder, registry, declarations, &mut imports, pos);
 
                     if !result.ends_with('[') {
                         result += &format!(",");
                     }
 
-                    result += &format!(" {}", renamed_comp.as_ref().unwrap_or(&sub_comp.get_ts_name(registry)));
+                    result += &format!(" {}", field_type);
                 }
                 result += "]";
 
                 let result = match repr {
                     Some((EnumRepresentation::Adjacently(tag, var), typ)) => {
-                        format!("{{\n\t{}: {};\n\t{}: {}\n}}", tag, typ, var, result)
+                        format!("{{\n\t{}: {};\n\t{}: {}\n}}", ts_property_key(&tag), ts_string_literal(typ), ts_property_key(&var), result)
                     }
                     Some((EnumRepresentation::Default, typ)) => {
-                        format!("{{\n\t{}: {}\n}}", typ, result)
+                        format!("{{\n\t{}: {}\n}}", ts_property_key(typ), result)
                     }
                     _ => result
                 };
@@ -257,35 +608,21 @@ impl InnerType {
                 (result, imports)
             },
             InnerType::NewType(refr) => {
-                let mut imports = HashMap::new();
-
-                let sub_comp = registry.get_indexed(&refr.id);
-
-                let renamed_comp = if let Some(import) = sub_comp.build(builder, registry) {
-                    let (name,_) = builder.get_type_and_import(&import, sub_comp.hash, 0);
-                    imports.insert(name.clone(), (name.clone(), refr.renamed.clone()));
-                    refr.renamed.clone().or_else(|| {
-                        match sub_comp.typ {
-                            Type::Array(_) => Some(format!("{}Array", name)),
-                            _ => Some(name)
-                        } 
-                    })
-                } else {
-                    refr.renamed.clone()
-                };
-
-                let _alternative = sub_comp.get_ts_name(registry);
+                let mut imports = BTreeMap::new();
 
-                let sub_name = renamed_comp.as_ref().unwrap_or(&_alternative);
-
-                update_declarations(declarations, sub_comp, &renamed_comp, registry);
+                let sub_name = Self::reference_type(refr, builder, registry, declarations, &mut imports, pos);
 
                 let result = match repr {
                     Some((EnumRepresentation::Adjacently(tag, var), typ)) => {
-                        format!("{{\n\t{}: \"{}\";\n\t{}: {}\n}}", tag, typ, var, sub_name)
+                        format!("{{\n\t{}: {};\n\t{}: {}\n}}", ts_property_key(&tag), ts_string_literal(typ), ts_property_key(&var), sub_name)
+                    }
+                    Some((EnumRepresentation::Internally(tag), typ)) => {
+                        // The fields of the inner value sit next to the tag, unit values disappear
+                        let tag = format!("{{\n\t{}: {}\n}}", ts_property_key(&tag), ts_string_literal(typ));
+                        if sub_name == "null" { tag } else { format!("{} & {}", tag, sub_name) }
                     }
                     Some((EnumRepresentation::Default, typ)) => {
-                        format!("{{\n\t{}: {}\n}}", typ, sub_name)
+                        format!("{{\n\t{}: {}\n}}", ts_property_key(typ), sub_name)
                     }
                     _ => sub_name.to_string()
                 };
@@ -294,14 +631,16 @@ impl InnerType {
             },
             InnerType::SimpleVariant(x) => {
                 let result = match repr {
-                    Some((EnumRepresentation::Adjacently(tag, _), typ)) => {
-                        format!("{{\n\t{}: \"{}\";\n}}", tag, typ)
+                    Some((EnumRepresentation::Adjacently(tag, _), typ)) |
+                    Some((EnumRepresentation::Internally(tag), typ)) => {
+                        format!("{{\n\t{}: {};\n}}", ts_property_key(&tag), ts_string_literal(typ))
                     },
-                    _ => format!(r#"{}"#, x)
+                    Some((EnumRepresentation::Untagged, _)) => String::from("null"),
+                    _ => ts_string_literal(x)
                 };
-                (format!(r#"{}"#, result), HashMap::new())
+                (result, BTreeMap::new())
             },
-            InnerType::Null => (String::from("null"), HashMap::new()),
+            InnerType::Null => (String::from("null"), BTreeMap::new()),
         };
 
         return (content, imports)
@@ -321,29 +660,61 @@ impl InnerType {
 #[derive(Clone, Debug)]
 pub enum Type {
     Array(ComponentReference),
+    /// A map with string like keys, serialized as a JSON object
+    Record(ComponentReference, ComponentReference),
+    /// `Option<T>`, written in place as `T | null`
+    Nullable(ComponentReference),
     Struct(InnerType),
-    Enum(EnumRepresentation, Vec<(String, InnerType)>),
+    Enum(EnumRepresentation, Vec<EnumVariant>),
+    /// A generic declaration applied to its type arguments
+    Generic(ComponentReference, Vec<ComponentReference>),
+    /// A type parameter inside a generic declaration
+    Param(String),
     SimpleType(String),
+    /// A 64 or 128 bit integer named by its Rust type, written according to `WideIntegers`
+    WideInteger(String),
     Any,
     None,
 }
 
 impl Type {
 
-    fn build_inner_query_string(&self, main_name: &str, field_name: &str) -> String {
+    /// Every component this type refers to directly
+    pub fn references(&self) -> Vec<&ComponentReference> {
+        match self {
+            Type::Generic(decl, args) => std::iter::once(decl).chain(args.iter()).collect(),
+            Type::Array(x) |
+            Type::Nullable(x) => vec![x],
+            Type::Record(k, v) => vec![k, v],
+            Type::Struct(inner) => inner.references(),
+            Type::Enum(_, variants) => variants.iter().flat_map(|v| v.inner.references()).collect(),
+            Type::Param(_) |
+            Type::SimpleType(_) |
+            Type::WideInteger(_) |
+            Type::Any |
+            Type::None => Vec::new(),
+        }
+    }
+
+    fn build_inner_query_string(&self, main_name: &str, accessor: &str, key: &str) -> String {
+        let optional_accessor = format!("?.{}", accessor.trim_start_matches('.'));
+        let key = key.replace('\\', "\\\\").replace('\'', "\\'");
+
         match self {
-            Type::Array(_) => format!("\tif({}?{} != null) {{ {}{}.forEach(val => __params.append('{}', val.toString())); }}", main_name, field_name, main_name, field_name, field_name.replace(".", "").replace("[", "").replace("]", "")),
-            _ => format!("\tif({}?{} != null) {{ __params.append('{}', {}{}.toString()) }}", main_name, field_name, field_name.replace(".", "").replace("[", "").replace("]", ""), main_name, field_name),
+            Type::Array(_) => format!("\tif({}{} != null) {{ {}{}.forEach(val => __params.append('{}', val.toString())); }}", main_name, optional_accessor, main_name, accessor, key),
+            _ => format!("\tif({}{} != null) {{ __params.append('{}', {}{}.toString()) }}", main_name, optional_accessor, key, main_name, accessor),
         }
     }
 
     pub fn build_query_string(&self, name: &str, registry: &GlobalTypeRegistry) -> String {
         match self {
             Type::Struct(inner) => inner.inner_query_string_builder(name, registry),
+            Type::Generic(decl, _) => registry.get_indexed(&decl.id).shape(Postion::Body).build_query_string(name, registry),
+            Type::Nullable(x) => registry.get_indexed(&x.id).shape(Postion::Body).build_query_string(name, registry),
             Type::Enum(repr, variants) => {
                 let mut strs = Vec::new();
-                for (_, variant) in variants {
-                    strs.push(variant.inner_query_string_builder(name, registry))
+                for variant in variants.iter().filter(|v| !v.skip) {
+                    strs.push(variant.inner.inner_query_string_builder(name, registry))
                 }
 
                 strs.join("\n")
@@ -352,15 +723,122 @@ impl Type {
         }
     }
 
-    pub fn build(&self, name: &str, hash: u64, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry) -> Option<String> {
+    /// Builds every variant that is not skipped with the representation it ends up with on the wire
+    fn build_variants<'a>(repr: &EnumRepresentation, variants: &'a [EnumVariant], builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, declarations: &mut BTreeMap<String, String>, imports: &mut BTreeMap<String,(String, Option<String>)>, pos: Postion) -> Vec<(&'a EnumVariant, String)> {
+        let mut built_variants = Vec::new();
+
+        for variant in variants.iter().filter(|v| !v.skip) {
+            let repr = if variant.untagged { EnumRepresentation::Untagged } else { repr.clone() };
+
+            let content = match (&repr, &variant.inner) {
+                // Any unknown tag is accepted by the `#[serde(other)]` variant
+                (EnumRepresentation::Internally(tag), InnerType::SimpleVariant(_)) |
+                (EnumRepresentation::Adjacently(tag, _), InnerType::SimpleVariant(_)) if variant.other => {
+                    format!("{{\n\t{}: string;\n}}", ts_property_key(tag))
+                },
+                _ => {
+                    let (content, variant_imports) = variant.inner.build(builder, registry, declarations, Some((repr, &variant.name)), pos);
+                    imports.extend(variant_imports);
+                    content
+                }
+            };
+
+            built_variants.push((variant, content));
+        }
+
+        built_variants
+    }
+
+    /// The variants of an externally tagged enum without any data, `None` for every other enum
+    fn unit_variants<'a>(repr: &EnumRepresentation, variants: &'a [EnumVariant], generics: &[String]) -> Option<Vec<&'a EnumVariant>> {
+        let variants = variants.iter().filter(|v| !v.skip).collect::<Vec<_>>();
+
+        let is_unit = matches!(repr, EnumRepresentation::Default) && generics.is_empty() && !variants.is_empty() &&
+            variants.iter().all(|v| !v.untagged && matches!(v.inner, InnerType::SimpleVariant(_)));
+
+        if is_unit { Some(variants) } else { None }
+    }
+
+    /// Fieldless enums are written as one union of their values, together with an array of all
+    /// values and the labels of the variants for the UI
+    fn build_unit_enum(name: &str, docs: &str, style: EnumStyle, variants: &[&EnumVariant]) -> (String, Vec<String>) {
+        let mut content = String::new();
+        let mut exports = vec![name.to_string()];
+
+        let member_docs = |variant: &EnumVariant| variant.docs.as_deref().map(|docs| ts_doc_comment(docs, "\t")).unwrap_or_default();
+
+        // How each value is written when it is used in the values array and the labels
+        let values = match style {
+            EnumStyle::Union => {
+                let values = variants.iter().map(|v| v.literal()).collect::<Vec<_>>();
+                content += &format!("\n\n{}export type {} = {}", docs, name, values.join(" | "));
+
+                values
+            },
+            EnumStyle::Enum => {
+                content += &format!("\n\n{}export enum {} {{", docs, name);
+                for variant in variants {
+                    content += &format!("\n{}\t{} = {},", member_docs(variant), ts_type_ident(&variant.name), variant.literal());
+                }
+                content += "\n}";
+
+                variants.iter().map(|v| format!("{}.{}", name, ts_type_ident(&v.name))).collect()
+            },
+            EnumStyle::Const => {
+                content += &format!("\n\n{}export const {} = {{", docs, name);
+                for variant in variants {
+                    content += &format!("\n{}\t{}: {},", member_docs(variant), ts_property_key(&variant.name), variant.literal());
+                }
+                content += "\n} as const";
+                content += &format!("\n\nexport type {0} = typeof {0}[keyof typeof {0}]", name);
+
+                variants.iter().map(|v| match is_ts_identifier(&v.name) {
+                    true => format!("{}.{}", name, v.name),
+                    false => format!("{}[{}]", name, ts_string_literal(&v.name)),
+                }).collect()
+            },
+        };
+
+        content += &format!("\n\nexport const {}Values = [{}] as const", name, values.join(", "));
+        exports.push(format!("{}Values", name));
+
+        if variants.iter().any(|v| v.label.is_some()) {
+            content += &format!("\n\nexport const {0}Labels: Record<{0}, string> = {{", name);
+            for (variant, value) in variants.iter().zip(values.iter()) {
+                let key = match (style, variant.discriminant) {
+                    (EnumStyle::Union, Some(_)) => value.clone(),
+                    (EnumStyle::Union, None) => ts_property_key(&variant.name),
+                    _ => format!("[{}]", value),
+                };
+                let label = variant.label.as_ref().unwrap_or(&variant.name);
+
+                content += &format!("\n\t{}: {},", key, ts_string_literal(label));
+            }
+            content += "\n}";
+            exports.push(format!("{}Labels", name));
+        }
+
+        (content, exports)
+    }
+
+    /// Writes the file for `component`, the component this type is the shape of
+    pub fn build(&self, name: &str, component: &Component, hash: u64, builder: &mut TypeBuilder, registry: &GlobalTypeRegistry, pos: Postion) -> Option<String> {
+        let generics = &component.generics;
+        let params = match generics.len() {
+            0 => String::new(),
+            _ => format!("<{}>", generics.join(", "))
+        };
+        let docs = component.docs.as_deref().map(|docs| ts_doc_comment(docs, "")).unwrap_or_default();
+
         match self {
             Self::Struct(fields) => {
                 let mut file = match builder.start_file(name, hash) {
                     Some(f) => f.lock().unwrap().clone(),
                     None => return Some(name.to_string()),
                 };
+                file.rust_path = component.rust_path.clone();
 
-                let (name,_) = builder.get_type_and_import(name, hash, 0);
+                let (name,_) = builder.get_type_and_import(name, hash, "");
 
                 let decl = fields.get_decl_type();
                 let ending = match decl {
@@ -368,19 +846,31 @@ impl Type {
                     _ => ""
                 };
 
-                file.content += &format!("\n\nexport {} {} {}", decl, name, ending);
+                file.content += &format!("\n\n{}export {} {}{} {}", docs, decl, name, params, ending);
+
+                let mut type_declarations = BTreeMap::new();
 
-                let mut type_declarations = HashMap::new();
+                let (content, imports) = fields.build(builder, registry, &mut type_declarations, None, pos);
 
-                let (content, imports) = fields.build(builder, registry, &mut type_declarations, None);
-                file.content += &content;
+                match fields {
+                    // A value of the inner type is only accepted after going through the constructor
+                    InnerType::NewType(_) if component.brand => {
+                        file.content += &format!("{} & {{ readonly __brand: {} }}", content, ts_string_literal(&name));
+                        file.content += &format!(
+                            "\n\nexport const {} = {}(value: {}): {}{} => value as {}{}",
+                            name, params, content, name, params, name, params
+                        );
+                    },
+                    _ => file.content += &content,
+                }
 
                 if type_declarations.len() > 0 {
                     file.type_defs.extend(type_declarations.values().map(&String::to_owned));
                 }
 
                 if imports.len() > 0 {
-                    file.imports.extend(imports.into_values());
+                    // Recursive types refer to themselves, importing the name would clash with the declaration
+                    file.imports.extend(imports.into_values().filter(|(import, renamed)| renamed.is_some() || *import != file.name));
                 }
 
                 file.exports.push(name.to_string());
@@ -397,41 +887,74 @@ impl Type {
                     Some(f) => f.lock().unwrap().clone(),
                     None => return Some(name.to_string()),
                 };
+                file.rust_path = component.rust_path.clone();
+
+                let (name,_) = builder.get_type_and_import(name, hash, "");
+
+                if let Some(units) = Self::unit_variants(repr, variants, generics) {
+                    let (content, exports) = Self::build_unit_enum(&name, &docs, component.enum_style, &units);
+                    file.content += &content;
+                    file.exports.extend(exports);
 
-                let (name,_) = builder.get_type_and_import(name, hash, 0);
+                    let mut guard = builder.get_file(&name, hash).expect(&name).lock().unwrap();
+                    *guard = file.clone();
+
+                    return Some(file.name.clone())
+                }
+
+                let mut taken_names = HashSet::from([name.clone()]);
+                taken_names.extend(generics.iter().cloned());
+
+                let mut type_declarations = BTreeMap::new();
+                let mut imports = BTreeMap::new();
+
+                let built_variants = Self::build_variants(repr, variants, builder, registry, &mut type_declarations, &mut imports, pos);
+
+                taken_names.extend(type_declarations.keys().cloned());
+                taken_names.extend(imports.values().map(|(import, renamed)| renamed.clone().unwrap_or(import.clone())));
+
+                if !type_declarations.is_empty() {
+                    file.type_defs.extend(type_declarations.values().map(&String::to_owned));
+                }
+
+                if !imports.is_empty() {
+                    // Recursive types refer to themselves, importing the name would clash with the declaration
+                    file.imports.extend(imports.into_values().filter(|(import, renamed)| renamed.is_some() || *import != file.name));
+                }
 
                 let mut all_variant_type_names = Vec::new();
 
-                for (variant, content) in variants {
-                    let decl = content.get_decl_type();
+                for (variant, content) in built_variants {
+                    let decl = variant.inner.get_decl_type();
                     let ending = match decl {
                         "type" => "= ",
                         _ => ""
                     };
 
-                    all_variant_type_names.push(variant.clone());
-
-                    file.content += &format!("\n\nexport {} {} {}", decl, variant, ending);
+                    // Variant aliases must not shadow imports, local aliases or the enum itself
+                    let mut variant_type_name = ts_type_ident(&variant.name);
+                    if taken_names.contains(&variant_type_name) {
+                        variant_type_name = format!("{}{}", name, variant_type_name);
+                    }
+                    let mut i = 1;
+                    while all_variant_type_names.contains(&variant_type_name) || taken_names.contains(&variant_type_name) {
+                        variant_type_name = format!("{}{}{}", name, ts_type_ident(&variant.name), i);
+                        i += 1;
+                    }
+                    all_variant_type_names.push(variant_type_name.clone());
 
-                    let mut type_declarations = HashMap::new();
+                    let variant_docs = variant.docs.as_deref().map(|docs| ts_doc_comment(docs, "")).unwrap_or_default();
 
-                    let (content, imports) = content.build(builder, registry, &mut type_declarations, Some((repr.clone(), &variant)));
+                    file.content += &format!("\n\n{}export {} {}{} {}", variant_docs, decl, variant_type_name, params, ending);
                     file.content += &content;
                     file.content += "\n";
 
-
-                    if type_declarations.len() > 0 {
-                        file.type_defs.extend(type_declarations.values().map(&String::to_owned));
-                    }
-
-                    if imports.len() > 0 {
-                        file.imports.extend(imports.into_values());
-                    }
-
-                    file.exports.push(name.to_string());
+                    file.exports.push(variant_type_name);
                 }
 
-                file.content += &format!("\n\nexport type {} = {}", name, all_variant_type_names.join(" | "));
+                let all_variant_types = all_variant_type_names.iter().map(|v| format!("{}{}", v, params)).collect::<Vec<_>>();
+
+                file.content += &format!("\n\n{}export type {}{} = {}", docs, name, params, all_variant_types.join(" | "));
 
                 file.exports.push(name.to_string());
 
@@ -450,12 +973,37 @@ impl Type {
             Self::Array(arr) => {
                 let sub_comp = registry.get_indexed(&arr.id);
 
-                return sub_comp.build(builder, registry);
+                return sub_comp.build(builder, registry, pos);
+
+            },
+            Self::Record(_, value) => {
+                let sub_comp = registry.get_indexed(&value.id);
+
+                return sub_comp.build(builder, registry, pos);
+            },
+            Self::Nullable(x) => {
+                registry.get_indexed(&x.id).build(builder, registry, pos);
+
+                return None
+            },
+            Self::Generic(decl, args) => {
+                for arg in args {
+                    registry.get_indexed(&arg.id).build(builder, registry, pos);
+                }
+
+                registry.get_indexed(&decl.id).build(builder, registry, pos);
 
+                return None
+            },
+            Self::Param(_) => {
+                return None
             },
             Self::SimpleType(simple) => {
                 return None
             },
+            Self::WideInteger(_) => {
+                return None
+            },
             Self::Any => {
                 return None
             },
@@ -466,6 +1014,84 @@ impl Type {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct EnumVariant {
+    /// The name of the variant on the wire
+    pub name: String,
+    pub inner: InnerType,
+    /// `#[serde(untagged)]` on the variant itself
+    pub untagged: bool,
+    /// `#[serde(other)]`, the variant any unknown tag is deserialized into
+    pub other: bool,
+    pub skip: bool,
+    /// The doc comment of the variant
+    pub docs: Option<String>,
+    /// Shown in the UI instead of the name (`#[ts(label = "...")]`)
+    pub label: Option<String>,
+    /// The numeric value of fieldless `serde_repr` enums, they are written as numbers
+    pub discriminant: Option<i64>,
+}
+
+impl EnumVariant {
+    pub fn new(name: &str, inner: InnerType) -> Self {
+        Self {
+            name: name.to_owned(),
+            inner,
+            untagged: false,
+            other: false,
+            skip: false,
+            docs: None,
+            label: None,
+            discriminant: None,
+        }
+    }
+
+    /// The value of a fieldless variant on the wire
+    pub fn literal(&self) -> String {
+        match self.discriminant {
+            Some(discriminant) => discriminant.to_string(),
+            None => ts_string_literal(&self.name),
+        }
+    }
+}
+
+/// How a fieldless enum is written in TypeScript (`#[ts(enum_style = "...")]`)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EnumStyle {
+    /// `export type Role = "Admin" | "User"`
+    #[default]
+    Union,
+    /// `export enum Role { Admin = "Admin", User = "User" }`
+    Enum,
+    /// `export const Role = { Admin: "Admin", User: "User" } as const`
+    Const,
+}
+
+/// How `u64`, `i64`, `u128`, `i128` and friends are written in TypeScript
+///
+/// JavaScript numbers are exact up to 2^53, serde writes wider integers as plain JSON numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WideIntegers {
+    /// `number`, values above 2^53 silently lose precision. The export warns about every wide type it writes
+    #[default]
+    Number,
+    /// `bigint | number`, the generated client parses responses with a reviver turning unsafe integers
+    /// into `bigint`s and writes `bigint`s back as plain JSON numbers. Needs `JSON.parse` source text access (ES2023+)
+    BigInt,
+    /// `string`, for servers writing wide integers with `serde_with`'s `DisplayFromStr`
+    String,
+}
+
+impl WideIntegers {
+    pub fn ts_type(&self) -> &'static str {
+        match self {
+            WideIntegers::Number => "number",
+            WideIntegers::BigInt => "bigint | number",
+            WideIntegers::String => "string",
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum EnumRepresentation {
     Default,
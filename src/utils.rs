@@ -16,4 +16,81 @@ pub fn clean_var_name(s: &str) -> String {
     .replace("<", "Of")
     .replace(">", "")
     .replace(",", "And")
-}
\ No newline at end of file
+    .replace(" ", "")
+}
+
+/// A route segment as a file or folder name, anything but letters, digits, `_`, `-` and the
+/// brackets of `[param]` becomes `_`, so `..` or separators can't leave the export folder
+pub fn file_name(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '[' | ']') { c } else { '_' })
+        .collect()
+}
+
+pub fn is_ts_identifier(s: &str) -> bool {
+    let mut c = s.chars();
+    match c.next() {
+        Some(f) if f.is_ascii_alphabetic() || f == '_' || f == '$' => c.all(|x| x.is_ascii_alphanumeric() || x == '_' || x == '$'),
+        _ => false,
+    }
+}
+
+/// Quotes a string so it can be used as a TypeScript string literal type
+pub fn ts_string_literal(s: &str) -> String {
+    serde_json::to_string(s).expect("Strings always serialize")
+}
+
+/// Property names that are not valid identifiers (e.g. `content-type`) have to be quoted
+pub fn ts_property_key(s: &str) -> String {
+    if is_ts_identifier(s) {
+        s.to_owned()
+    } else {
+        ts_string_literal(s)
+    }
+}
+
+/// Writes documentation as a JSDoc block in front of a declaration, every line starts with `indent`
+pub fn ts_doc_comment(docs: &str, indent: &str) -> String {
+    let docs = docs.replace("*/", "*\\/");
+    let lines = docs.lines().collect::<Vec<_>>();
+
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{}/** {} */\n", indent, line),
+        lines => {
+            let mut result = format!("{}/**\n", indent);
+            for line in lines {
+                match line.is_empty() {
+                    true => result += &format!("{} *\n", indent),
+                    false => result += &format!("{} * {}\n", indent, line),
+                }
+            }
+            result + &format!("{} */\n", indent)
+        }
+    }
+}
+
+/// Turns a wire name like `manage-user` into something usable as a type name (`ManageUser`)
+pub fn ts_type_ident(s: &str) -> String {
+    let mut result = String::new();
+    let mut upper = true;
+
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            if upper {
+                result.extend(c.to_uppercase());
+            } else {
+                result.push(c);
+            }
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+
+    match result.chars().next() {
+        None => String::from("_"),
+        Some(f) if f.is_ascii_digit() => format!("_{}", result),
+        Some(_) => result,
+    }
+}